@@ -0,0 +1,267 @@
+use crate::entry::Entry;
+use crate::pdf::inline;
+use crate::resume::{Interest, Language, Reference, Resume, Skill};
+use crate::timeline::*;
+use std::error::Error;
+use std::fs::write;
+use std::path::PathBuf;
+
+const CONTACT: &str = "Contact";
+const SUMMARY: &str = "Summary";
+const EXPERIENCE: &str = "Experience";
+const EDUCATION: &str = "Education";
+const VOLUNTEERING: &str = "Volunteering";
+const SKILLS: &str = "Skills";
+const LANGUAGES: &str = "Languages";
+const PROJECTS: &str = "Projects";
+const AWARDS: &str = "Awards";
+const CERTIFICATES: &str = "Certifications";
+const PUBLICATIONS: &str = "Publications";
+const INTERESTS: &str = "Interests";
+const REFERENCES: &str = "References";
+
+/// Piece of the single column layout read by applicant tracking systems.
+#[derive(Debug, Clone)]
+pub enum Block {
+    Name(String),
+    Heading(String),
+    Title(String),
+    Text(String),
+    Item(String),
+    Break,
+}
+
+/// Every section of the resume under a standard heading, in a linear
+/// reading order.
+pub fn blocks(resume: &Resume) -> Vec<Block> {
+    let basics = &resume.basics;
+    let mut blocks = vec![Block::Name(basics.name.clone())];
+    if !basics.label.is_empty() {
+        blocks.push(Block::Text(basics.label.clone()));
+    }
+
+    blocks.push(Block::Heading(CONTACT.to_string()));
+    basics
+        .email
+        .iter()
+        .chain(basics.phone.iter())
+        .for_each(|contact| blocks.push(Block::Text(contact.clone())));
+    if let Some(location) = &basics.location {
+        location
+            .address_lines()
+            .into_iter()
+            .for_each(|line| blocks.push(Block::Text(line)));
+    }
+    if let Some(website) = &basics.website {
+        blocks.push(Block::Text(website.clone()));
+    }
+    basics.profiles.iter().for_each(|profile| {
+        let account = if profile.url.is_empty() {
+            &profile.username
+        } else {
+            &profile.url
+        };
+        blocks.push(Block::Text(format!("{}: {}", profile.network, account)));
+    });
+
+    if let Some(summary) = &basics.summary {
+        blocks.push(Block::Heading(SUMMARY.to_string()));
+        blocks.push(Block::Text(summary.clone()));
+    }
+
+    let experience: Timeline = resume.work.iter().cloned().map(Event::from).collect();
+    let education: Timeline = resume.education.iter().cloned().map(Event::from).collect();
+    let volunteering: Timeline = resume.volunteer.iter().cloned().map(Event::from).collect();
+    write_events(&mut blocks, EXPERIENCE, &experience.events());
+    write_events(&mut blocks, EDUCATION, &education.events());
+    write_events(&mut blocks, VOLUNTEERING, &volunteering.events());
+
+    if !resume.skills.is_empty() {
+        blocks.push(Block::Heading(SKILLS.to_string()));
+        resume.skills.iter().for_each(
+            |Skill {
+                 name,
+                 level,
+                 keywords,
+                 ..
+             }| {
+                let mut text = name.clone();
+                if let Some(level) = level {
+                    text = format!("{} ({})", text, level);
+                }
+                if !keywords.is_empty() {
+                    text = format!("{}: {}", text, keywords.join(", "));
+                }
+                blocks.push(Block::Item(text));
+            },
+        );
+    }
+
+    if !resume.languages.is_empty() {
+        blocks.push(Block::Heading(LANGUAGES.to_string()));
+        resume
+            .languages
+            .iter()
+            .for_each(|Language { language, fluency }| {
+                let text = match fluency {
+                    Some(fluency) => format!("{}: {}", language, fluency),
+                    None => language.clone(),
+                };
+                blocks.push(Block::Item(text));
+            });
+    }
+
+    write_entries(&mut blocks, PROJECTS, &resume.projects);
+    write_entries(&mut blocks, AWARDS, &resume.awards);
+    write_entries(&mut blocks, CERTIFICATES, &resume.certificates);
+    write_entries(&mut blocks, PUBLICATIONS, &resume.publications);
+
+    if !resume.interests.is_empty() {
+        blocks.push(Block::Heading(INTERESTS.to_string()));
+        resume
+            .interests
+            .iter()
+            .for_each(|Interest { name, keywords }| {
+                let text = if keywords.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}: {}", name, keywords.join(", "))
+                };
+                blocks.push(Block::Item(text));
+            });
+    }
+
+    if !resume.references.is_empty() {
+        blocks.push(Block::Heading(REFERENCES.to_string()));
+        resume
+            .references
+            .iter()
+            .for_each(|Reference { name, reference }| {
+                blocks.push(Block::Text(format!("\"{}\"", reference)));
+                blocks.push(Block::Text(format!("- {}", name)));
+                blocks.push(Block::Break);
+            });
+        blocks.pop();
+    }
+    blocks
+}
+
+fn write_events(blocks: &mut Vec<Block>, heading: &str, events: &[Event]) {
+    if events.is_empty() {
+        return;
+    }
+    blocks.push(Block::Heading(heading.to_string()));
+    events.iter().enumerate().for_each(|(i, event)| {
+        if i > 0 {
+            blocks.push(Block::Break);
+        }
+        let (institution, location) = event.institution_and_location();
+        let title = if event.label.is_empty() {
+            institution.to_string()
+        } else {
+            format!("{}, {}", event.label, institution)
+        };
+        blocks.push(Block::Title(title));
+        let period = format_period(&event.start_date, event.end_date.as_ref());
+        let location = location.trim();
+        blocks.push(Block::Text(if location.is_empty() {
+            period
+        } else {
+            format!("{} | {}", location, period)
+        }));
+        if let Some(summary) = &event.summary {
+            blocks.push(Block::Text(summary.clone()));
+        }
+        event
+            .highlights
+            .iter()
+            .for_each(|highlight| blocks.push(Block::Item(highlight.clone())));
+    });
+}
+
+fn write_entries<T>(blocks: &mut Vec<Block>, heading: &str, items: &[T])
+where
+    T: Clone + Into<Entry>,
+{
+    if items.is_empty() {
+        return;
+    }
+    blocks.push(Block::Heading(heading.to_string()));
+    items
+        .iter()
+        .cloned()
+        .map(Into::<Entry>::into)
+        .enumerate()
+        .for_each(|(i, entry)| {
+            if i > 0 {
+                blocks.push(Block::Break);
+            }
+            blocks.push(Block::Title(entry.title));
+            if !entry.subtitle.is_empty() {
+                blocks.push(Block::Text(entry.subtitle));
+            }
+            if let Some(website) = entry.website {
+                blocks.push(Block::Text(website));
+            }
+            if let Some(summary) = entry.summary {
+                blocks.push(Block::Text(summary));
+            }
+            entry
+                .highlights
+                .into_iter()
+                .for_each(|highlight| blocks.push(Block::Item(highlight)));
+        });
+}
+
+/// Plain text version of the resume, for applicant tracking systems.
+pub struct Txt {
+    blocks: Vec<Block>,
+}
+
+impl Txt {
+    pub fn new(resume: &Resume) -> Self {
+        Self {
+            blocks: blocks(resume),
+        }
+    }
+
+    pub fn save(&self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
+        debug!("Generating text: {:?}", filename);
+        let mut text = String::new();
+        self.blocks.iter().for_each(|block| match block {
+            Block::Name(name) => {
+                text.push_str(&name.to_uppercase());
+                text.push('\n');
+            }
+            Block::Heading(heading) => {
+                text.push('\n');
+                text.push_str(&heading.to_uppercase());
+                text.push_str("\n\n");
+            }
+            Block::Title(line) => {
+                text.push_str(line);
+                text.push('\n');
+            }
+            Block::Text(line) => {
+                text.push_str(&plain(line));
+                text.push('\n');
+            }
+            Block::Item(item) => {
+                text.push_str("- ");
+                text.push_str(&plain(item));
+                text.push('\n');
+            }
+            Block::Break => text.push('\n'),
+        });
+        write(filename, text)?;
+        Ok(())
+    }
+}
+
+/// Text without its inline Markdown markers.
+fn plain(text: &str) -> String {
+    inline::parse(text)
+        .iter()
+        .map(|span| span.text.as_str())
+        .collect()
+}
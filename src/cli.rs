@@ -1,24 +1,148 @@
+use crate::format::Format;
+use crate::pdf::{AboutPlacement, Paper, Section, Template};
+use crate::preview::parse_dpi;
 use std::path::PathBuf;
+use structopt::clap::ArgMatches;
 use structopt::StructOpt;
 
+/// Arguments describing a single output, by their name and their flag, that
+/// `build` reads from its manifest instead.
+const SINGLE_OUTPUT_ARGS: [(&str, &str); 20] = [
+    ("filename", "<filename>"),
+    ("output", "<output>"),
+    ("format", "--format"),
+    ("theme", "--theme"),
+    ("regular font", "--font-regular"),
+    ("bold font", "--font-bold"),
+    ("light font", "--font-light"),
+    ("italic font", "--font-italic"),
+    ("omit-left-column", "--omit-left-column"),
+    ("volunteer-section", "--volunteer-section"),
+    ("normalize-institutions", "--normalize-institutions"),
+    ("paper", "--paper"),
+    ("about", "--about"),
+    ("template", "--template"),
+    ("left sections", "--left-sections"),
+    ("main sections", "--main-sections"),
+    ("tags", "--tags"),
+    ("excluded tags", "--exclude-tags"),
+    ("ats", "--ats"),
+    ("landscape", "--landscape"),
+];
+
 #[derive(StructOpt, Debug)]
 #[structopt(about = "Resume generator")]
 pub struct Cli {
     #[structopt(parse(from_os_str))]
-    /// Resume to generate, (Json or Toml)
-    pub filename: PathBuf,
-    #[structopt(parse(from_os_str), default_value = "resume.pdf")]
-    /// Generated resume
-    pub output: PathBuf,
-    #[structopt(parse(from_os_str), long = "gs", name = "path")]
+    /// Resume to generate, (Json or Toml), required unless building a manifest
+    pub filename: Option<PathBuf>,
+    #[structopt(parse(from_os_str))]
+    /// Generated resume [default: resume.<format>]
+    pub output: Option<PathBuf>,
+    #[structopt(long = "format")]
+    /// Output format: pdf, html, txt, md, docx, tex, svg or png, guessed from the output extension by default
+    pub format: Option<Format>,
+    #[structopt(parse(from_os_str), long = "gs", name = "path", global = true)]
     /// Ghostscript executable
     pub ghostscript: Option<PathBuf>,
+    #[structopt(parse(from_os_str), long = "theme", name = "theme")]
+    /// Theme file, (Json or Toml), overriding the resume meta.theme section
+    pub theme: Option<PathBuf>,
+    #[structopt(parse(from_os_str), long = "font-regular", name = "regular font")]
+    /// Regular font file (TTF or OTF)
+    pub font_regular: Option<PathBuf>,
+    #[structopt(parse(from_os_str), long = "font-bold", name = "bold font")]
+    /// Bold font file (TTF or OTF)
+    pub font_bold: Option<PathBuf>,
+    #[structopt(parse(from_os_str), long = "font-light", name = "light font")]
+    /// Light font file (TTF or OTF)
+    pub font_light: Option<PathBuf>,
+    #[structopt(parse(from_os_str), long = "font-italic", name = "italic font")]
+    /// Italic font file (TTF or OTF)
+    pub font_italic: Option<PathBuf>,
+    #[structopt(long = "omit-left-column")]
+    /// Do not repeat the left column on continuation pages
+    pub omit_left_column: bool,
+    #[structopt(long = "volunteer-section")]
+    /// Render volunteering as its own section instead of in the timeline
+    pub volunteer_section: bool,
+    #[structopt(long = "normalize-institutions")]
+    /// Group roles at institutions whose names only differ by case, punctuation or legal form
+    pub normalize_institutions: bool,
+    #[structopt(long = "paper", default_value = "a4")]
+    /// Page size: a4, letter, legal or <width>x<height> in mm
+    pub paper: Paper,
+    #[structopt(long = "about", default_value = "left")]
+    /// Column of the summary: left, under the name, or right, above the timeline
+    pub about: AboutPlacement,
+    #[structopt(long = "template", default_value = "sidebar")]
+    /// Page layout: sidebar, classic single column or compact one-pager
+    pub template: Template,
+    #[structopt(
+        long = "left-sections",
+        name = "left sections",
+        require_delimiter = true
+    )]
+    /// Comma separated sections of the left column, in order, overriding the template and meta.sections.left
+    pub left_sections: Option<Vec<Section>>,
+    #[structopt(
+        long = "main-sections",
+        name = "main sections",
+        require_delimiter = true
+    )]
+    /// Comma separated sections of the main column, in order, overriding the template and meta.sections.main
+    pub main_sections: Option<Vec<Section>>,
+    #[structopt(long = "tags", name = "tags", require_delimiter = true)]
+    /// Comma separated tags, keeping only the tagged work, education, skills, projects and highlights having one of them
+    pub tags: Vec<String>,
+    #[structopt(
+        long = "exclude-tags",
+        name = "excluded tags",
+        require_delimiter = true
+    )]
+    /// Comma separated tags, leaving out the work, education, skills, projects and highlights having one of them
+    pub exclude_tags: Vec<String>,
+    #[structopt(long = "ats")]
+    /// Single column PDF without icons, picture nor QR code, for applicant tracking systems
+    pub ats: bool,
+    #[structopt(
+        long = "dpi",
+        default_value = "96",
+        parse(try_from_str = parse_dpi),
+        global = true
+    )]
+    /// Resolution of the svg and png previews of the first page
+    pub dpi: f32,
+    #[structopt(long = "landscape")]
+    /// Use the landscape orientation
+    pub landscape: bool,
     #[structopt(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum Command {
+    /// Generates every output listed in a manifest, in parallel
+    Build {
+        #[structopt(parse(from_os_str))]
+        /// Manifest of the outputs, (Toml)
+        manifest: PathBuf,
+    },
 }
 
 impl Cli {
     pub fn log_level(&self) -> Option<log::LevelFilter> {
         self.verbose.log_level().map(|l| l.to_level_filter())
     }
+
+    /// First argument of `matches` only describing a single output, their
+    /// default values aside.
+    pub fn single_output_arg(matches: &ArgMatches) -> Option<&'static str> {
+        SINGLE_OUTPUT_ARGS
+            .iter()
+            .find(|(name, _)| matches.occurrences_of(name) > 0)
+            .map(|(_, flag)| *flag)
+    }
 }
@@ -0,0 +1,431 @@
+use crate::entry::Entry;
+use crate::html::escape;
+use crate::pdf::Paper;
+use crate::resume::{phone_uri, Interest, Language, Reference, Resume, Skill};
+use crate::theme::Theme;
+use crate::timeline::*;
+use crate::titles::*;
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const COURSES: &str = "Courses";
+const TWIPS_PER_MM: f64 = 1440. / 25.4;
+/// Margin around the pages, in millimeters.
+const PAGE_MARGIN: f64 = 20.;
+/// The first two relationships of the document are its styles and numbering.
+const FIRST_LINK_ID: usize = 3;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+<Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>
+</Types>"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+</Relationships>"#;
+
+const NUMBERING: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:abstractNum w:abstractNumId="0">
+<w:multiLevelType w:val="singleLevel"/>
+<w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="&#8226;"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="360" w:hanging="360"/></w:pPr></w:lvl>
+</w:abstractNum>
+<w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+</w:numbering>"#;
+
+/// Piece of text of a paragraph.
+enum Run<'a> {
+    Text(&'a str),
+    Bold(&'a str),
+    Italic(&'a str),
+    Link(&'a str, &'a str),
+}
+
+/// Paragraphs of the document along with the targets of their hyperlinks.
+#[derive(Default)]
+struct Body {
+    xml: String,
+    links: Vec<String>,
+}
+
+impl Body {
+    fn paragraph(&mut self, style: &str, runs: &[Run]) {
+        self.xml.push_str(&format!(
+            "<w:p><w:pPr><w:pStyle w:val=\"{}\"/></w:pPr>",
+            style
+        ));
+        runs.iter().for_each(|run| {
+            let run = match run {
+                Run::Text(text) => text_run("", text),
+                Run::Bold(text) => text_run("<w:b/>", text),
+                Run::Italic(text) => text_run("<w:i/>", text),
+                Run::Link(text, uri) => {
+                    let id = FIRST_LINK_ID + self.links.len();
+                    self.links.push(uri.to_string());
+                    format!(
+                        "<w:hyperlink r:id=\"rId{}\">{}</w:hyperlink>",
+                        id,
+                        text_run("<w:rStyle w:val=\"Hyperlink\"/>", text)
+                    )
+                }
+            };
+            self.xml.push_str(&run);
+        });
+        self.xml.push_str("</w:p>\n");
+    }
+
+    fn heading(&mut self, title: &str) {
+        self.paragraph("Heading1", &[Run::Text(title)]);
+    }
+
+    fn highlights(&mut self, highlights: &[String]) {
+        highlights
+            .iter()
+            .for_each(|highlight| self.paragraph("ListBullet", &[Run::Text(highlight)]));
+    }
+}
+
+/// Word document with the sections of the PDF, the timeline being in the
+/// same order.
+pub struct Docx {
+    resume: Resume,
+    theme: Theme,
+    paper: Paper,
+    volunteer_section: bool,
+}
+
+impl Docx {
+    pub fn new(resume: Resume, theme: Theme, paper: Paper, volunteer_section: bool) -> Self {
+        Self {
+            resume,
+            theme,
+            paper,
+            volunteer_section,
+        }
+    }
+
+    pub fn save(&self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
+        debug!("Generating docx: {:?}", filename);
+        let mut body = Body::default();
+        self.write_bio(&mut body);
+        self.write_info(&mut body);
+        self.write_social(&mut body);
+        self.write_languages(&mut body);
+        self.write_skills(&mut body);
+        self.write_timeline(&mut body);
+        write_entries(&mut body, PROJECTS, &self.resume.projects);
+        write_entries(&mut body, AWARDS, &self.resume.awards);
+        write_entries(&mut body, CERTIFICATES, &self.resume.certificates);
+        write_entries(&mut body, PUBLICATIONS, &self.resume.publications);
+        self.write_interests(&mut body);
+        self.write_references(&mut body);
+
+        let parts = [
+            ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+            ("_rels/.rels", RELATIONSHIPS.to_string()),
+            ("word/document.xml", self.document(&body.xml)),
+            ("word/_rels/document.xml.rels", relationships(&body.links)),
+            ("word/styles.xml", self.styles()),
+            ("word/numbering.xml", NUMBERING.to_string()),
+        ];
+        let mut zip = ZipWriter::new(File::create(filename)?);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (name, content) in parts.iter() {
+            zip.start_file(*name, options)?;
+            zip.write_all(content.as_bytes())?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+
+    fn write_bio(&self, body: &mut Body) {
+        let basics = &self.resume.basics;
+        body.paragraph("Title", &[Run::Text(&basics.name)]);
+        if !basics.label.is_empty() {
+            body.paragraph("Subtitle", &[Run::Text(&basics.label)]);
+        }
+        if let Some(summary) = &basics.summary {
+            body.paragraph("Normal", &[Run::Text(summary)]);
+        }
+    }
+
+    fn write_info(&self, body: &mut Body) {
+        let basics = &self.resume.basics;
+        body.heading(INFO);
+        if let Some(email) = &basics.email {
+            let uri = format!("mailto:{}", email);
+            body.paragraph("Normal", &[Run::Bold("Email: "), Run::Link(email, &uri)]);
+        }
+        if let Some(phone) = &basics.phone {
+            let uri = phone_uri(phone);
+            body.paragraph("Normal", &[Run::Bold("Phone: "), Run::Link(phone, &uri)]);
+        }
+        if let Some(location) = &basics.location {
+            let lines = location.address_lines();
+            if !lines.is_empty() {
+                let location = lines.join(", ");
+                body.paragraph("Normal", &[Run::Bold("Location: "), Run::Text(&location)]);
+            }
+        }
+        if let Some(birthday) = &basics.birthday {
+            let birthday = format!("{} ({}yo)", birthday.format("%d %b %Y"), birthday.age());
+            body.paragraph("Normal", &[Run::Bold("Birthday: "), Run::Text(&birthday)]);
+        }
+        if let Some(website) = &basics.website {
+            body.paragraph(
+                "Normal",
+                &[Run::Bold("Website: "), Run::Link(website, website)],
+            );
+        }
+    }
+
+    fn write_social(&self, body: &mut Body) {
+        let profiles = &self.resume.basics.profiles;
+        if profiles.is_empty() {
+            return;
+        }
+        body.heading(SOCIALS);
+        profiles.iter().for_each(|profile| {
+            let network = format!("{}: ", profile.network);
+            let username = if profile.username.is_empty() {
+                &profile.url
+            } else {
+                &profile.username
+            };
+            let account = if profile.url.is_empty() {
+                Run::Text(username)
+            } else {
+                Run::Link(username, &profile.url)
+            };
+            body.paragraph("ListBullet", &[Run::Bold(&network), account]);
+        });
+    }
+
+    fn write_languages(&self, body: &mut Body) {
+        if self.resume.languages.is_empty() {
+            return;
+        }
+        body.heading(LANGUAGES);
+        self.resume
+            .languages
+            .iter()
+            .for_each(|Language { language, fluency }| {
+                let fluency = fluency
+                    .as_ref()
+                    .map(|fluency| format!(": {}", fluency))
+                    .unwrap_or_default();
+                body.paragraph("ListBullet", &[Run::Bold(language), Run::Text(&fluency)]);
+            });
+    }
+
+    fn write_skills(&self, body: &mut Body) {
+        if self.resume.skills.is_empty() {
+            return;
+        }
+        body.heading(SKILLS);
+        self.resume.skills.iter().for_each(
+            |Skill {
+                 name,
+                 level,
+                 keywords,
+                 ..
+             }| {
+                let mut text = String::new();
+                if let Some(level) = level {
+                    text.push_str(&format!(" ({})", level));
+                }
+                if !keywords.is_empty() {
+                    text.push_str(&format!(": {}", keywords.join(", ")));
+                }
+                body.paragraph("ListBullet", &[Run::Bold(name), Run::Text(&text)]);
+            },
+        );
+    }
+
+    fn write_timeline(&self, body: &mut Body) {
+        let (timeline, volunteering) = Timeline::from_resume(&self.resume, self.volunteer_section);
+        write_events(body, TIMELINE, &timeline.events());
+        write_events(body, VOLUNTEERING, &volunteering.events());
+    }
+
+    fn write_interests(&self, body: &mut Body) {
+        if self.resume.interests.is_empty() {
+            return;
+        }
+        body.heading(INTERESTS);
+        self.resume
+            .interests
+            .iter()
+            .for_each(|Interest { name, keywords }| {
+                let keywords = if keywords.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", keywords.join(", "))
+                };
+                body.paragraph("ListBullet", &[Run::Bold(name), Run::Text(&keywords)]);
+            });
+    }
+
+    fn write_references(&self, body: &mut Body) {
+        if self.resume.references.is_empty() {
+            return;
+        }
+        body.heading(REFERENCES);
+        self.resume
+            .references
+            .iter()
+            .for_each(|Reference { name, reference }| {
+                let quote = format!("\u{201c}{}\u{201d}", reference);
+                let author = format!("\u{2014} {}", name);
+                body.paragraph("Quote", &[Run::Text(&quote)]);
+                body.paragraph("Normal", &[Run::Bold(&author)]);
+            });
+    }
+
+    fn document(&self, xml: &str) -> String {
+        let Paper { width, height } = self.paper;
+        let orientation = if width.0 > height.0 {
+            " w:orient=\"landscape\""
+        } else {
+            ""
+        };
+        format!(
+            include_str!("docx/document.xml"),
+            body = xml,
+            width = twips(width.0),
+            height = twips(height.0),
+            orientation = orientation,
+            margin = twips(PAGE_MARGIN),
+        )
+    }
+
+    fn styles(&self) -> String {
+        let sizes = &self.theme.font_sizes;
+        let half_points = |size: f64| (size * 2.).round();
+        format!(
+            include_str!("docx/styles.xml"),
+            primary = self.theme.colors.primary.to_hex(),
+            name = half_points(sizes.name),
+            heading = half_points(sizes.heading),
+            title = half_points(sizes.title),
+            text = half_points(sizes.text),
+            small = half_points(sizes.small),
+        )
+    }
+}
+
+fn write_events(body: &mut Body, title: &str, events: &[Event]) {
+    if events.is_empty() {
+        return;
+    }
+    body.heading(title);
+    events.iter().for_each(|event| {
+        let (institution, location) = event.institution_and_location();
+        let institution = match &event.website {
+            Some(website) => Run::Link(institution, website),
+            None => Run::Text(institution),
+        };
+        let label = format!("{}, ", event.label);
+        if event.label.is_empty() {
+            body.paragraph("Heading2", &[institution]);
+        } else {
+            body.paragraph("Heading2", &[Run::Text(&label), institution]);
+        }
+
+        let period = format_period(&event.start_date, event.end_date.as_ref());
+        let location = location.trim();
+        let details = if location.is_empty() {
+            period
+        } else {
+            format!("{} \u{b7} {}", location, period)
+        };
+        body.paragraph("Normal", &[Run::Italic(&details)]);
+        if let Some(summary) = &event.summary {
+            body.paragraph("Normal", &[Run::Text(summary)]);
+        }
+        if !event.highlights.is_empty() {
+            if let EventType::Education = event.event_type {
+                body.paragraph("Normal", &[Run::Bold(COURSES)]);
+            }
+            body.highlights(&event.highlights);
+        }
+    });
+}
+
+fn write_entries<T>(body: &mut Body, title: &str, items: &[T])
+where
+    T: Clone + Into<Entry>,
+{
+    if items.is_empty() {
+        return;
+    }
+    body.heading(title);
+    items
+        .iter()
+        .cloned()
+        .map(Into::<Entry>::into)
+        .for_each(|entry| {
+            match &entry.website {
+                Some(website) => body.paragraph("Heading2", &[Run::Link(&entry.title, website)]),
+                None => body.paragraph("Heading2", &[Run::Text(&entry.title)]),
+            }
+            if !entry.subtitle.is_empty() {
+                body.paragraph("Normal", &[Run::Italic(&entry.subtitle)]);
+            }
+            if let Some(summary) = &entry.summary {
+                body.paragraph("Normal", &[Run::Text(summary)]);
+            }
+            body.highlights(&entry.highlights);
+        });
+}
+
+/// Relationships of the document to its styles, numbering and the targets of
+/// its hyperlinks.
+fn relationships(links: &[String]) -> String {
+    let links: String = links
+        .iter()
+        .enumerate()
+        .map(|(i, uri)| {
+            format!(
+                "<Relationship Id=\"rId{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink\" Target=\"{}\" TargetMode=\"External\"/>\n",
+                FIRST_LINK_ID + i,
+                escape(uri)
+            )
+        })
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>
+{}</Relationships>"#,
+        links
+    )
+}
+
+/// Run of text, the line breaks being kept.
+fn text_run(properties: &str, text: &str) -> String {
+    let text = text
+        .split('\n')
+        .map(|line| format!("<w:t xml:space=\"preserve\">{}</w:t>", escape(line)))
+        .collect::<Vec<_>>()
+        .join("<w:br/>");
+    if properties.is_empty() {
+        format!("<w:r>{}</w:r>", text)
+    } else {
+        format!("<w:r><w:rPr>{}</w:rPr>{}</w:r>", properties, text)
+    }
+}
+
+fn twips(mm: f64) -> f64 {
+    (mm * TWIPS_PER_MM).round()
+}
@@ -0,0 +1,85 @@
+use crate::resume::{Award, Certificate, Project, Publication};
+
+use crate::timeline::{format_period, DATE_FORMAT};
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub title: String,
+    pub website: Option<String>,
+    pub subtitle: String,
+    pub summary: Option<String>,
+    pub highlights: Vec<String>,
+}
+
+impl From<Award> for Entry {
+    fn from(award: Award) -> Self {
+        let date = award
+            .date
+            .map(|date| date.format(DATE_FORMAT))
+            .unwrap_or_default();
+        Self {
+            title: award.title,
+            website: None,
+            subtitle: join_non_empty(&[award.awarder, date]),
+            summary: award.summary,
+            highlights: vec![],
+        }
+    }
+}
+
+impl From<Publication> for Entry {
+    fn from(publication: Publication) -> Self {
+        let release_date = publication
+            .release_date
+            .map(|date| date.format(DATE_FORMAT))
+            .unwrap_or_default();
+        Self {
+            title: publication.name,
+            website: publication.website,
+            subtitle: join_non_empty(&[publication.publisher, release_date]),
+            summary: publication.summary,
+            highlights: vec![],
+        }
+    }
+}
+
+impl From<Project> for Entry {
+    fn from(project: Project) -> Self {
+        let period = project
+            .start_date
+            .map(|start_date| format_period(&start_date, project.end_date.as_ref()))
+            .unwrap_or_default();
+        Self {
+            title: project.name,
+            website: project.website,
+            subtitle: period,
+            summary: project.description,
+            highlights: project.highlights.into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl From<Certificate> for Entry {
+    fn from(certificate: Certificate) -> Self {
+        let date = certificate
+            .date
+            .map(|date| date.format(DATE_FORMAT))
+            .unwrap_or_default();
+        Self {
+            title: certificate.name,
+            website: certificate.website,
+            subtitle: join_non_empty(&[certificate.issuer, date]),
+            summary: None,
+            highlights: vec![],
+        }
+    }
+}
+
+fn join_non_empty(parts: &[String]) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
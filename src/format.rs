@@ -0,0 +1,89 @@
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Kind of document generated from the resume.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Format {
+    #[default]
+    Pdf,
+    Html,
+    Txt,
+    Markdown,
+    Docx,
+    Latex,
+    Svg,
+    Png,
+}
+
+#[derive(Debug)]
+pub struct UnknownFormatError {
+    format: String,
+}
+
+impl fmt::Display for UnknownFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown format: {}, expected pdf, html, txt, md, docx, tex, svg or png",
+            self.format
+        )
+    }
+}
+
+impl Error for UnknownFormatError {}
+
+impl Format {
+    /// Format matching the extension of `path`, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(OsStr::to_str)
+            .and_then(|extension| extension.parse().ok())
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Pdf => "pdf",
+            Format::Html => "html",
+            Format::Txt => "txt",
+            Format::Markdown => "md",
+            Format::Docx => "docx",
+            Format::Latex => "tex",
+            Format::Svg => "svg",
+            Format::Png => "png",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormatError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "pdf" => Ok(Format::Pdf),
+            "html" | "htm" => Ok(Format::Html),
+            "txt" | "text" => Ok(Format::Txt),
+            "md" | "markdown" => Ok(Format::Markdown),
+            "docx" => Ok(Format::Docx),
+            "tex" | "latex" => Ok(Format::Latex),
+            "svg" => Ok(Format::Svg),
+            "png" => Ok(Format::Png),
+            _ => Err(UnknownFormatError {
+                format: format.to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<String> for Format {
+    type Error = UnknownFormatError;
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        format.parse()
+    }
+}
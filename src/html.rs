@@ -0,0 +1,542 @@
+use crate::entry::Entry;
+use crate::pdf::inline;
+use crate::resume::{phone_uri, Interest, Language, Reference, Resume, Skill};
+use crate::theme::{HighlightStyle, Theme};
+use crate::timeline::*;
+use crate::titles::*;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt::{self, Write};
+use std::fs::{read, write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub struct MissingFileError {
+    path: PathBuf,
+    err: std::io::Error,
+}
+
+impl fmt::Display for MissingFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot read {:?}: {}", self.path, self.err)
+    }
+}
+
+impl Error for MissingFileError {}
+
+/// Self-contained HTML page with the same sections as the PDF, the styles,
+/// picture and fonts being inlined.
+pub struct Html {
+    resume: Resume,
+    theme: Theme,
+    volunteer_section: bool,
+    normalize_institutions: bool,
+}
+
+impl Html {
+    pub fn new(
+        resume: Resume,
+        theme: Theme,
+        volunteer_section: bool,
+        normalize_institutions: bool,
+    ) -> Self {
+        Self {
+            resume,
+            theme,
+            volunteer_section,
+            normalize_institutions,
+        }
+    }
+
+    pub fn save(&self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
+        debug!("Generating html: {:?}", filename);
+        write(filename, self.render()?)?;
+        Ok(())
+    }
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut html = String::new();
+        writeln!(html, "<!DOCTYPE html>")?;
+        writeln!(html, "<html>")?;
+        writeln!(html, "<head>")?;
+        writeln!(html, "<meta charset=\"utf-8\">")?;
+        writeln!(
+            html,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+        )?;
+        writeln!(html, "<title>{}</title>", escape(&self.resume.basics.name))?;
+        writeln!(html, "<style>\n{}</style>", self.style()?)?;
+        writeln!(html, "</head>")?;
+        writeln!(html, "<body>")?;
+
+        writeln!(html, "<aside>")?;
+        self.write_picture(&mut html)?;
+        self.write_bio(&mut html)?;
+        self.write_info(&mut html)?;
+        self.write_social(&mut html)?;
+        self.write_languages(&mut html)?;
+        self.write_skills(&mut html)?;
+        writeln!(html, "</aside>")?;
+
+        writeln!(html, "<main>")?;
+        self.write_timeline(&mut html)?;
+        self.write_entries(&mut html, PROJECTS, &self.resume.projects)?;
+        self.write_entries(&mut html, AWARDS, &self.resume.awards)?;
+        self.write_entries(&mut html, CERTIFICATES, &self.resume.certificates)?;
+        self.write_entries(&mut html, PUBLICATIONS, &self.resume.publications)?;
+        self.write_interests(&mut html)?;
+        self.write_references(&mut html)?;
+        writeln!(html, "</main>")?;
+
+        writeln!(html, "</body>")?;
+        writeln!(html, "</html>")?;
+        Ok(html)
+    }
+
+    fn style(&self) -> Result<String, Box<dyn Error>> {
+        let Theme {
+            colors,
+            font_sizes,
+            line_heights,
+            columns,
+            spacing,
+            highlights,
+            fonts,
+        } = &self.theme;
+        let mut css = String::new();
+
+        let faces = [
+            (&fonts.regular, "normal", "normal"),
+            (&fonts.bold, "bold", "normal"),
+            (&fonts.light, "300", "normal"),
+            (&fonts.italic, "normal", "italic"),
+        ];
+        for (path, weight, style) in faces.iter() {
+            if let Some(path) = path {
+                writeln!(
+                    css,
+                    "@font-face {{ font-family: \"Resume\"; font-weight: {}; font-style: {}; src: url({}); }}",
+                    weight,
+                    style,
+                    data_uri(path)?
+                )?;
+            }
+        }
+
+        write!(
+            css,
+            include_str!("html.css"),
+            primary = format!("#{}", colors.primary.to_hex()),
+            secondary = format!("#{}", colors.secondary.to_hex()),
+            left_width = columns.left_width,
+            padding = columns.padding,
+            page_margin = spacing.page_margin,
+            event = spacing.event,
+            entry = spacing.entry,
+            section = spacing.section,
+            name = font_sizes.name,
+            heading = font_sizes.heading,
+            title = font_sizes.title,
+            text = font_sizes.text,
+            small = font_sizes.small,
+            name_line = line_heights.name,
+            list_line = line_heights.list,
+            text_line = line_heights.text,
+            bullet = highlights.bullet.replace('\\', "\\\\").replace('"', "\\\""),
+        )?;
+        Ok(css)
+    }
+
+    fn write_picture(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        if let Some(picture) = &self.resume.basics.picture {
+            match data_uri(Path::new(picture)) {
+                Err(err) => warn!("Picture: {}", err),
+                Ok(uri) => writeln!(html, "<img class=\"picture\" src=\"{}\" alt=\"\">", uri)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_bio(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        let basics = &self.resume.basics;
+        writeln!(html, "<header>")?;
+        writeln!(html, "<h1>{}</h1>", escape(&basics.name))?;
+        if !basics.label.is_empty() {
+            writeln!(html, "<p class=\"label\">{}</p>", escape(&basics.label))?;
+        }
+        writeln!(html, "</header>")?;
+        if let Some(summary) = &basics.summary {
+            writeln!(html, "<section>")?;
+            writeln!(html, "<h2>{}</h2>", ABOUT)?;
+            writeln!(html, "<p>{}</p>", markdown(summary))?;
+            writeln!(html, "</section>")?;
+        }
+        Ok(())
+    }
+
+    fn write_info(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        let basics = &self.resume.basics;
+        let mut items = vec![];
+        if let Some(email) = &basics.email {
+            items.push(format!("<a href=\"mailto:{0}\">{0}</a>", escape(email)));
+        }
+        if let Some(phone) = &basics.phone {
+            items.push(format!(
+                "<a href=\"{}\">{}</a>",
+                phone_uri(phone),
+                escape(phone)
+            ));
+        }
+        if let Some(location) = &basics.location {
+            let lines: Vec<String> = location
+                .address_lines()
+                .iter()
+                .map(|line| escape(line))
+                .collect();
+            if !lines.is_empty() {
+                items.push(format!("<address>{}</address>", lines.join("<br>")));
+            }
+        }
+        if let Some(birthday) = &basics.birthday {
+            items.push(format!(
+                "{} ({}yo)",
+                birthday.format("%d %b %Y"),
+                birthday.age()
+            ));
+        }
+        if let Some(website) = &basics.website {
+            items.push(format!("<a href=\"{0}\">{0}</a>", escape(website)));
+        }
+        if items.is_empty() {
+            return Ok(());
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h2>{}</h2>", INFO)?;
+        writeln!(html, "<ul>")?;
+        for item in items {
+            writeln!(html, "<li>{}</li>", item)?;
+        }
+        writeln!(html, "</ul>")?;
+        writeln!(html, "</section>")?;
+        Ok(())
+    }
+
+    fn write_social(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        let profiles = &self.resume.basics.profiles;
+        if profiles.is_empty() {
+            return Ok(());
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h2>{}</h2>", SOCIALS)?;
+        writeln!(html, "<ul>")?;
+        for profile in profiles {
+            let username = if profile.username.is_empty() {
+                &profile.url
+            } else {
+                &profile.username
+            };
+            let text = format!(
+                "<span class=\"network\">{}</span> {}",
+                escape(&profile.network),
+                escape(username)
+            );
+            if profile.url.is_empty() {
+                writeln!(html, "<li>{}</li>", text)?;
+            } else {
+                writeln!(
+                    html,
+                    "<li><a href=\"{}\">{}</a></li>",
+                    escape(&profile.url),
+                    text
+                )?;
+            }
+        }
+        writeln!(html, "</ul>")?;
+        writeln!(html, "</section>")?;
+        Ok(())
+    }
+
+    fn write_languages(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.languages.is_empty() {
+            return Ok(());
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h2>{}</h2>", LANGUAGES)?;
+        writeln!(html, "<ul>")?;
+        for Language { language, fluency } in &self.resume.languages {
+            write!(html, "<li><strong>{}</strong>", escape(language))?;
+            if let Some(fluency) = fluency {
+                write!(html, " <span class=\"light\">{}</span>", escape(fluency))?;
+            }
+            writeln!(html, "</li>")?;
+        }
+        writeln!(html, "</ul>")?;
+        writeln!(html, "</section>")?;
+        Ok(())
+    }
+
+    fn write_skills(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.skills.is_empty() {
+            return Ok(());
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h2>{}</h2>", SKILLS)?;
+        writeln!(html, "<ul>")?;
+        for Skill {
+            name,
+            level,
+            keywords,
+            ..
+        } in &self.resume.skills
+        {
+            write!(html, "<li><strong>{}</strong>", escape(name))?;
+            if let Some(level) = level {
+                write!(html, " <span class=\"light\">({})</span>", escape(level))?;
+            }
+            writeln!(html)?;
+            self.write_highlights(html, keywords)?;
+            writeln!(html, "</li>")?;
+        }
+        writeln!(html, "</ul>")?;
+        writeln!(html, "</section>")?;
+        Ok(())
+    }
+
+    fn write_timeline(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        let (timeline, volunteering) = Timeline::from_resume(&self.resume, self.volunteer_section);
+        let normalize = self.normalize_institutions;
+        self.write_events(html, &timeline.groups(normalize))?;
+
+        let groups = volunteering.groups(normalize);
+        if !groups.is_empty() {
+            writeln!(html, "<section>")?;
+            writeln!(html, "<h2>{}</h2>", VOLUNTEERING)?;
+            self.write_events(html, &groups)?;
+            writeln!(html, "</section>")?;
+        }
+        Ok(())
+    }
+
+    fn write_events(&self, html: &mut String, groups: &[Group]) -> Result<(), Box<dyn Error>> {
+        writeln!(html, "<div class=\"timeline\">")?;
+        for (group, index, event) in groups.iter().flat_map(|group| {
+            group
+                .events
+                .iter()
+                .enumerate()
+                .map(move |(index, event)| (group, index, event))
+        }) {
+            let same_as_previous = index > 0;
+            let class = match (same_as_previous, &event.event_type) {
+                (true, _) => "same",
+                (false, EventType::Work) => "work",
+                (false, EventType::Education) => "education",
+                (false, EventType::Volunteer) => "volunteer",
+            };
+            writeln!(html, "<article class=\"event {}\">", class)?;
+
+            writeln!(html, "<div class=\"heading\">")?;
+            if !same_as_previous {
+                let (institution, location) = event.institution_and_location();
+                match &event.website {
+                    Some(website) => writeln!(
+                        html,
+                        "<h3><a href=\"{}\">{}</a></h3>",
+                        escape(website),
+                        escape(institution)
+                    )?,
+                    None => writeln!(html, "<h3>{}</h3>", escape(institution))?,
+                }
+                if !location.is_empty() {
+                    writeln!(html, "<p>{}</p>", escape(location.trim()))?;
+                }
+                if group.events.len() > 1 {
+                    let (start_date, end_date) = group.period();
+                    writeln!(
+                        html,
+                        "<p class=\"light\">{}</p>",
+                        format_period(&start_date, end_date.as_ref())
+                    )?;
+                }
+            }
+            writeln!(html, "<p>{}</p>", escape(&event.label))?;
+            writeln!(
+                html,
+                "<p class=\"light\">{}</p>",
+                format_period(&event.start_date, event.end_date.as_ref())
+            )?;
+            writeln!(html, "</div>")?;
+
+            writeln!(html, "<div class=\"details\">")?;
+            if let Some(summary) = &event.summary {
+                writeln!(html, "<p>{}</p>", escape(summary))?;
+            }
+            self.write_highlights(html, &event.highlights)?;
+            writeln!(html, "</div>")?;
+
+            writeln!(html, "</article>")?;
+        }
+        writeln!(html, "</div>")?;
+        Ok(())
+    }
+
+    fn write_entries<T>(
+        &self,
+        html: &mut String,
+        title: &str,
+        items: &[T],
+    ) -> Result<(), Box<dyn Error>>
+    where
+        T: Clone + Into<Entry>,
+    {
+        if items.is_empty() {
+            return Ok(());
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h2>{}</h2>", title)?;
+        for entry in items.iter().cloned().map(Into::<Entry>::into) {
+            writeln!(html, "<article class=\"entry\">")?;
+            match &entry.website {
+                Some(website) => writeln!(
+                    html,
+                    "<h3><a href=\"{}\">{}</a></h3>",
+                    escape(website),
+                    escape(&entry.title)
+                )?,
+                None => writeln!(html, "<h3>{}</h3>", escape(&entry.title))?,
+            }
+            if !entry.subtitle.is_empty() {
+                writeln!(html, "<p class=\"light\">{}</p>", escape(&entry.subtitle))?;
+            }
+            if let Some(summary) = &entry.summary {
+                writeln!(html, "<p>{}</p>", escape(summary))?;
+            }
+            self.write_highlights(html, &entry.highlights)?;
+            writeln!(html, "</article>")?;
+        }
+        writeln!(html, "</section>")?;
+        Ok(())
+    }
+
+    fn write_interests(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.interests.is_empty() {
+            return Ok(());
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h2>{}</h2>", INTERESTS)?;
+        writeln!(html, "<div class=\"chips\">")?;
+        for Interest { name, keywords } in &self.resume.interests {
+            writeln!(
+                html,
+                "<span class=\"chip highlighted\">{}</span>",
+                escape(name)
+            )?;
+            for keyword in keywords {
+                writeln!(html, "<span class=\"chip\">{}</span>", escape(keyword))?;
+            }
+        }
+        writeln!(html, "</div>")?;
+        writeln!(html, "</section>")?;
+        Ok(())
+    }
+
+    fn write_references(&self, html: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.references.is_empty() {
+            return Ok(());
+        }
+        writeln!(html, "<section>")?;
+        writeln!(html, "<h2>{}</h2>", REFERENCES)?;
+        for Reference { name, reference } in &self.resume.references {
+            writeln!(html, "<blockquote>")?;
+            writeln!(html, "<p>\u{201c}{}\u{201d}</p>", escape(reference))?;
+            writeln!(html, "<footer>\u{2014} {}</footer>", escape(name))?;
+            writeln!(html, "</blockquote>")?;
+        }
+        writeln!(html, "</section>")?;
+        Ok(())
+    }
+
+    /// Writes the highlights as a bullet list or as chips, depending on the
+    /// theme.
+    fn write_highlights(
+        &self,
+        html: &mut String,
+        highlights: &[String],
+    ) -> Result<(), Box<dyn Error>> {
+        if highlights.is_empty() {
+            return Ok(());
+        }
+        match self.theme.highlights.style {
+            HighlightStyle::List => {
+                writeln!(html, "<ul class=\"highlights\">")?;
+                for highlight in highlights {
+                    writeln!(html, "<li>{}</li>", escape(highlight))?;
+                }
+                writeln!(html, "</ul>")?;
+            }
+            HighlightStyle::Chips => {
+                writeln!(html, "<div class=\"highlights chips\">")?;
+                for highlight in highlights {
+                    writeln!(html, "<span class=\"chip\">{}</span>", escape(highlight))?;
+                }
+                writeln!(html, "</div>")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Escapes the characters having a meaning in HTML or XML text and attributes.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    text.chars().for_each(|c| match c {
+        '&' => escaped.push_str("&amp;"),
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '"' => escaped.push_str("&quot;"),
+        '\'' => escaped.push_str("&#39;"),
+        c => escaped.push(c),
+    });
+    escaped
+}
+
+/// Renders the inline Markdown of `text` as escaped HTML.
+fn markdown(text: &str) -> String {
+    let mut html = String::new();
+    inline::parse(text).iter().for_each(|span| {
+        let mut text = escape(&span.text);
+        if span.italic {
+            text = format!("<em>{}</em>", text);
+        }
+        if span.bold {
+            text = format!("<strong>{}</strong>", text);
+        }
+        if let Some(link) = &span.link {
+            text = format!("<a href=\"{}\">{}</a>", escape(link), text);
+        }
+        html.push_str(&text);
+    });
+    html
+}
+
+/// Inlines the file at `path` as a base64 data URI.
+fn data_uri(path: &Path) -> Result<String, MissingFileError> {
+    let bytes = read(path).map_err(|err| MissingFileError {
+        path: path.to_path_buf(),
+        err,
+    })?;
+    let extension = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_lowercase);
+    let mime = match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("bmp") => "image/bmp",
+        Some("ttf") => "font/ttf",
+        Some("otf") => "font/otf",
+        _ => "application/octet-stream",
+    };
+    Ok(format!("data:{};base64,{}", mime, base64::encode(bytes)))
+}
@@ -0,0 +1,512 @@
+use crate::entry::Entry;
+use crate::pdf::Paper;
+use crate::resume::{Award, Certificate, Interest, Language, Location, Reference, Resume, Skill};
+use crate::theme::Theme;
+use crate::timeline::*;
+use crate::titles::*;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs::{canonicalize, write};
+use std::path::PathBuf;
+
+const EDUCATION: &str = "Education";
+const EXPERIENCE: &str = "Experience";
+const COURSES: &str = "Courses";
+/// Networks having a `\social` command in moderncv.
+const SOCIAL_NETWORKS: [&str; 12] = [
+    "linkedin",
+    "xing",
+    "twitter",
+    "github",
+    "gitlab",
+    "stackoverflow",
+    "bitbucket",
+    "skype",
+    "orcid",
+    "researchgate",
+    "researcherid",
+    "telegram",
+];
+
+/// LaTeX source using the moderncv document class, for those who want to
+/// hand-tune the final output.
+pub struct Latex {
+    resume: Resume,
+    theme: Theme,
+    paper: Paper,
+}
+
+impl Latex {
+    pub fn new(resume: Resume, theme: Theme, paper: Paper) -> Self {
+        Self {
+            resume,
+            theme,
+            paper,
+        }
+    }
+
+    pub fn save(&self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
+        debug!("Generating latex: {:?}", filename);
+        write(filename, self.render()?)?;
+        Ok(())
+    }
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut latex = String::new();
+        self.write_preamble(&mut latex)?;
+        self.write_personal_data(&mut latex)?;
+        writeln!(latex, "\n\\begin{{document}}\n\\makecvtitle")?;
+
+        let experience: Timeline = self.resume.work.iter().cloned().map(Event::from).collect();
+        let education: Timeline = self
+            .resume
+            .education
+            .iter()
+            .cloned()
+            .map(Event::from)
+            .collect();
+        let volunteering: Timeline = self
+            .resume
+            .volunteer
+            .iter()
+            .cloned()
+            .map(Event::from)
+            .collect();
+        write_events(&mut latex, EDUCATION, &education.events())?;
+        write_events(&mut latex, EXPERIENCE, &experience.events())?;
+        write_events(&mut latex, VOLUNTEERING, &volunteering.events())?;
+
+        self.write_skills(&mut latex)?;
+        self.write_languages(&mut latex)?;
+        write_entries(&mut latex, PROJECTS, &self.resume.projects)?;
+        self.write_awards(&mut latex)?;
+        self.write_certificates(&mut latex)?;
+        self.write_publications(&mut latex)?;
+        self.write_interests(&mut latex)?;
+        self.write_references(&mut latex)?;
+        writeln!(latex, "\n\\end{{document}}")?;
+        Ok(latex)
+    }
+
+    fn write_preamble(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        let font_size = self.theme.font_sizes.text.round().clamp(10., 12.);
+        let Paper { width, height } = self.paper;
+        writeln!(latex, "\\documentclass[{}pt,sans]{{moderncv}}", font_size)?;
+        writeln!(latex, "\\moderncvstyle{{classic}}")?;
+        writeln!(latex, "\\moderncvcolor{{blue}}")?;
+        writeln!(latex, "\\usepackage[utf8]{{inputenc}}")?;
+        writeln!(latex, "\\usepackage[T1]{{fontenc}}")?;
+        writeln!(
+            latex,
+            "\\usepackage[paperwidth={}mm,paperheight={}mm,margin={}mm]{{geometry}}",
+            width.0, height.0, self.theme.columns.padding
+        )?;
+        let primary = self.theme.colors.primary.to_hex().to_uppercase();
+        writeln!(latex, "\\definecolor{{color0}}{{HTML}}{{{}}}", primary)?;
+        writeln!(latex, "\\definecolor{{color1}}{{HTML}}{{{}}}", primary)?;
+        Ok(())
+    }
+
+    fn write_personal_data(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        let basics = &self.resume.basics;
+        let (first_name, last_name) = basics
+            .name
+            .trim()
+            .rsplit_once(' ')
+            .unwrap_or((basics.name.trim(), ""));
+        writeln!(
+            latex,
+            "\n\\name{{{}}}{{{}}}",
+            escape(first_name),
+            escape(last_name)
+        )?;
+        if !basics.label.is_empty() {
+            writeln!(latex, "\\title{{{}}}", escape(&basics.label))?;
+        }
+        if let Some(location) = &basics.location {
+            write_address(latex, location)?;
+        }
+        if let Some(phone) = &basics.phone {
+            writeln!(latex, "\\phone[mobile]{{{}}}", escape(phone))?;
+        }
+        if let Some(email) = &basics.email {
+            writeln!(latex, "\\email{{{}}}", escape(email))?;
+        }
+        if let Some(website) = &basics.website {
+            let website = website
+                .trim_start_matches("https://")
+                .trim_start_matches("http://");
+            writeln!(latex, "\\homepage{{{}}}", escape(website))?;
+        }
+
+        let mut extra_info = vec![];
+        if let Some(birthday) = &basics.birthday {
+            extra_info.push(format!("Born {}", birthday.format("%d %b %Y")));
+        }
+        for profile in &basics.profiles {
+            let network = profile.network.to_lowercase();
+            if SOCIAL_NETWORKS.contains(&network.as_str()) && !profile.username.is_empty() {
+                writeln!(
+                    latex,
+                    "\\social[{}]{{{}}}",
+                    network,
+                    escape(&profile.username)
+                )?;
+            } else if !profile.url.is_empty() {
+                let username = if profile.username.is_empty() {
+                    &profile.url
+                } else {
+                    &profile.username
+                };
+                extra_info.push(format!(
+                    "{}: \\href{{{}}}{{{}}}",
+                    escape(&profile.network),
+                    escape_url(&profile.url),
+                    escape(username)
+                ));
+            }
+        }
+        if !extra_info.is_empty() {
+            writeln!(latex, "\\extrainfo{{{}}}", extra_info.join(", "))?;
+        }
+        if let Some(picture) = &basics.picture {
+            // The path is made absolute, as LaTeX resolves it against the
+            // output rather than the working directory, and taken verbatim,
+            // underscores included
+            match canonicalize(picture) {
+                Err(err) => warn!("Picture: {}: {}", picture, err),
+                Ok(path) => writeln!(
+                    latex,
+                    "\\photo[64pt][0.4pt]{{\\detokenize{{{}}}}}",
+                    path.display()
+                )?,
+            }
+        }
+        if let Some(summary) = &basics.summary {
+            writeln!(latex, "\\quote{{{}}}", escape(summary))?;
+        }
+        Ok(())
+    }
+
+    fn write_skills(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.skills.is_empty() {
+            return Ok(());
+        }
+        writeln!(latex, "\n\\section{{{}}}", SKILLS)?;
+        for Skill {
+            name,
+            level,
+            keywords,
+            ..
+        } in &self.resume.skills
+        {
+            let keywords = escape(&keywords.join(", "));
+            match level {
+                Some(level) => writeln!(
+                    latex,
+                    "\\cvitemwithcomment{{{}}}{{{}}}{{{}}}",
+                    escape(name),
+                    keywords,
+                    escape(level)
+                )?,
+                None => writeln!(latex, "\\cvitem{{{}}}{{{}}}", escape(name), keywords)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_languages(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.languages.is_empty() {
+            return Ok(());
+        }
+        writeln!(latex, "\n\\section{{{}}}", LANGUAGES)?;
+        for Language { language, fluency } in &self.resume.languages {
+            writeln!(
+                latex,
+                "\\cvitem{{{}}}{{{}}}",
+                escape(language),
+                escape(fluency.as_deref().unwrap_or_default())
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_awards(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.awards.is_empty() {
+            return Ok(());
+        }
+        writeln!(latex, "\n\\section{{{}}}", AWARDS)?;
+        for Award {
+            title,
+            date,
+            awarder,
+            summary,
+        } in &self.resume.awards
+        {
+            writeln!(
+                latex,
+                "\\cventry{{{}}}{{{}}}{{{}}}{{}}{{}}{{{}}}",
+                date.map(|date| date.format(DATE_FORMAT))
+                    .unwrap_or_default(),
+                escape(title),
+                escape(awarder),
+                escape(summary.as_deref().unwrap_or_default())
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_certificates(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.certificates.is_empty() {
+            return Ok(());
+        }
+        writeln!(latex, "\n\\section{{{}}}", CERTIFICATES)?;
+        for certificate in &self.resume.certificates {
+            let Certificate { date, issuer, .. } = certificate;
+            let name = entry_title(&Entry::from(certificate.clone()));
+            let certificate = if issuer.is_empty() {
+                format!("\\textbf{{{}}}", name)
+            } else {
+                format!("\\textbf{{{}}}, {}", name, escape(issuer))
+            };
+            writeln!(
+                latex,
+                "\\cvitem{{{}}}{{{}}}",
+                date.map(|date| date.format(DATE_FORMAT))
+                    .unwrap_or_default(),
+                certificate
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Publications as a bibliography, moderncv giving it its own section.
+    fn write_publications(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.publications.is_empty() {
+            return Ok(());
+        }
+        writeln!(latex, "\n\\renewcommand{{\\refname}}{{{}}}", PUBLICATIONS)?;
+        writeln!(latex, "\\begin{{thebibliography}}{{9}}")?;
+        for (i, entry) in self
+            .resume
+            .publications
+            .iter()
+            .cloned()
+            .map(Entry::from)
+            .enumerate()
+        {
+            write!(
+                latex,
+                "\\bibitem{{publication{}}} \\textbf{{{}}}",
+                i + 1,
+                entry_title(&entry)
+            )?;
+            if !entry.subtitle.is_empty() {
+                write!(latex, ", {}", escape(&entry.subtitle))?;
+            }
+            if let Some(summary) = &entry.summary {
+                write!(latex, ". {}", escape(summary))?;
+            }
+            writeln!(latex)?;
+        }
+        writeln!(latex, "\\end{{thebibliography}}")?;
+        Ok(())
+    }
+
+    fn write_interests(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.interests.is_empty() {
+            return Ok(());
+        }
+        writeln!(latex, "\n\\section{{{}}}", INTERESTS)?;
+        for Interest { name, keywords } in &self.resume.interests {
+            writeln!(
+                latex,
+                "\\cvitem{{{}}}{{{}}}",
+                escape(name),
+                escape(&keywords.join(", "))
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_references(&self, latex: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.references.is_empty() {
+            return Ok(());
+        }
+        writeln!(latex, "\n\\section{{{}}}", REFERENCES)?;
+        for Reference { name, reference } in &self.resume.references {
+            writeln!(
+                latex,
+                "\\cvitem{{{}}}{{\\emph{{``{}''}}}}",
+                escape(name),
+                escape(reference)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// moderncv takes up to three address lines: the street, city and country.
+fn write_address(latex: &mut String, location: &Location) -> Result<(), Box<dyn Error>> {
+    let mut lines: Vec<String> = location
+        .address_lines()
+        .iter()
+        .map(|line| escape(line))
+        .collect();
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let rest = lines.split_off(lines.len().min(2)).join(", ");
+    lines.resize(2, String::new());
+    writeln!(
+        latex,
+        "\\address{{{}}}{{{}}}{{{}}}",
+        lines[0], lines[1], rest
+    )?;
+    Ok(())
+}
+
+fn write_events(latex: &mut String, title: &str, events: &[Event]) -> Result<(), Box<dyn Error>> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    writeln!(latex, "\n\\section{{{}}}", title)?;
+    for event in events {
+        let (institution, location) = event.institution_and_location();
+        let institution = match &event.website {
+            Some(website) => format!(
+                "\\href{{{}}}{{{}}}",
+                escape_url(website),
+                escape(institution)
+            ),
+            None => escape(institution),
+        };
+        let mut description = vec![];
+        if let Some(summary) = &event.summary {
+            description.push(escape(summary));
+        }
+        if !event.highlights.is_empty() {
+            if let EventType::Education = event.event_type {
+                description.push(format!("\\emph{{{}}}", COURSES));
+            }
+            description.push(itemize(&event.highlights));
+        }
+        writeln!(
+            latex,
+            "\\cventry{{{}}}{{{}}}{{{}}}{{{}}}{{}}{{{}}}",
+            escape(&format_period(&event.start_date, event.end_date.as_ref())),
+            escape(&event.label),
+            institution,
+            escape(location.trim()),
+            description.join("\n")
+        )?;
+    }
+    Ok(())
+}
+
+fn write_entries<T>(latex: &mut String, title: &str, items: &[T]) -> Result<(), Box<dyn Error>>
+where
+    T: Clone + Into<Entry>,
+{
+    if items.is_empty() {
+        return Ok(());
+    }
+    writeln!(latex, "\n\\section{{{}}}", title)?;
+    for entry in items.iter().cloned().map(Into::<Entry>::into) {
+        let mut description = vec![];
+        if let Some(summary) = &entry.summary {
+            description.push(escape(summary));
+        }
+        if !entry.highlights.is_empty() {
+            description.push(itemize(&entry.highlights));
+        }
+        writeln!(
+            latex,
+            "\\cventry{{{}}}{{{}}}{{}}{{}}{{}}{{{}}}",
+            escape(&entry.subtitle),
+            entry_title(&entry),
+            description.join("\n")
+        )?;
+    }
+    Ok(())
+}
+
+/// Title of `entry`, linking to its website if any.
+fn entry_title(entry: &Entry) -> String {
+    match &entry.website {
+        Some(website) => format!(
+            "\\href{{{}}}{{{}}}",
+            escape_url(website),
+            escape(&entry.title)
+        ),
+        None => escape(&entry.title),
+    }
+}
+
+fn itemize(items: &[String]) -> String {
+    let items: String = items
+        .iter()
+        // The empty group keeps items starting with `[` from being read as
+        // a label
+        .map(|item| format!("\\item{{}} {}\n", escape(item)))
+        .collect();
+    format!("\\begin{{itemize}}\n{}\\end{{itemize}}", items)
+}
+
+/// Escapes the LaTeX special characters, line breaks being kept.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    text.chars().for_each(|c| match c {
+        '\\' => escaped.push_str("\\textbackslash{}"),
+        '~' => escaped.push_str("\\textasciitilde{}"),
+        '^' => escaped.push_str("\\textasciicircum{}"),
+        '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+            escaped.push('\\');
+            escaped.push(c);
+        }
+        '\n' => escaped.push_str("\\newline{}"),
+        c => escaped.push(c),
+    });
+    escaped
+}
+
+/// Escapes the characters `\href` does not take verbatim.
+fn escape_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    url.chars().for_each(|c| match c {
+        '\\' | '%' | '#' | '{' | '}' => {
+            escaped.push('\\');
+            escaped.push(c);
+        }
+        c => escaped.push(c),
+    });
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(escape("R&D 100% #1"), "R\\&D 100\\% \\#1");
+        assert_eq!(escape("snake_case {x} $5"), "snake\\_case \\{x\\} \\$5");
+        assert_eq!(
+            escape("a\\b~c^d"),
+            "a\\textbackslash{}b\\textasciitilde{}c\\textasciicircum{}d"
+        );
+    }
+
+    #[test]
+    fn keeps_line_breaks() {
+        assert_eq!(escape("first\nsecond"), "first\\newline{}second");
+    }
+
+    #[test]
+    fn braces_items() {
+        assert_eq!(
+            itemize(&["[beta] release".to_string()]),
+            "\\begin{itemize}\n\\item{} [beta] release\n\\end{itemize}"
+        );
+    }
+}
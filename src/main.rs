@@ -8,12 +8,31 @@ extern crate printpdf;
 extern crate qrcode_generator;
 extern crate simple_logger;
 
+mod ats;
 mod cli;
+mod docx;
+mod entry;
+mod format;
+mod html;
+mod latex;
+mod manifest;
+mod markdown;
 mod pdf;
+mod preview;
 mod resume;
+mod theme;
+mod timeline;
+mod titles;
+use ats::Txt;
 use cli::Cli;
-use pdf::Pdf;
-use resume::Resume;
+use docx::Docx;
+use format::Format;
+use html::Html;
+use latex::Latex;
+use markdown::Markdown;
+use pdf::{Layout, Options, Pdf};
+use preview::Preview;
+use resume::{Resume, TagFilter};
 use simple_logger::SimpleLogger;
 use std::borrow::Cow;
 use std::env;
@@ -21,35 +40,169 @@ use std::error::Error;
 use std::fs::{canonicalize, remove_file, rename};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use structopt::clap;
 use structopt::StructOpt;
+use theme::{Fonts, Theme};
 
 fn main() {
-    let opt = Cli::from_args();
+    let matches = Cli::clap().get_matches();
+    let opt = Cli::from_clap(&matches);
     if let Some(level) = opt.log_level() {
         SimpleLogger::new().with_level(level).init().unwrap();
     }
-    let filename = opt.filename;
-    let output = opt.output;
-    let ghostscript = opt.ghostscript;
-    std::process::exit(
-        match Resume::from_path(filename)
-            .and_then(|resume| {
-                let pdf = Pdf::new(resume)?;
-                pdf.save(&output)
-            })
-            .and_then(|_| optimize_pdf(&output, ghostscript))
-        {
-            Ok(()) => {
-                info!("Resume generated");
-                0
-            }
-
+    if let Some(cli::Command::Build { manifest }) = opt.command {
+        if let Some(flag) = Cli::single_output_arg(&matches) {
+            clap::Error::with_description(
+                &format!(
+                    "The argument '{}' only applies to a single output and cannot be used with 'build'",
+                    flag
+                ),
+                clap::ErrorKind::ArgumentConflict,
+            )
+            .exit()
+        }
+        std::process::exit(match manifest::build(&manifest, opt.ghostscript, opt.dpi) {
+            Ok(0) => 0,
+            Ok(_) => 1,
             Err(err) => {
                 error!("{}", err);
                 1
             }
+        });
+    }
+    let filename = opt.filename.unwrap_or_else(|| {
+        clap::Error::with_description(
+            "The following required arguments were not provided:\n    <filename>",
+            clap::ErrorKind::MissingRequiredArgument,
+        )
+        .exit()
+    });
+    let output = opt.output;
+    let format = opt
+        .format
+        .or_else(|| output.as_deref().and_then(Format::from_path))
+        .unwrap_or_default();
+    let output =
+        output.unwrap_or_else(|| PathBuf::from("resume").with_extension(format.extension()));
+    let job = Job {
+        filename,
+        output,
+        format,
+        theme: opt.theme,
+        fonts: Fonts {
+            regular: opt.font_regular,
+            bold: opt.font_bold,
+            light: opt.font_light,
+            italic: opt.font_italic,
         },
-    )
+        options: Options {
+            omit_left_column: opt.omit_left_column,
+            ats: opt.ats,
+            volunteer_section: opt.volunteer_section,
+            normalize_institutions: opt.normalize_institutions,
+            paper: if opt.landscape {
+                opt.paper.landscape()
+            } else {
+                opt.paper
+            },
+            about: opt.about,
+            template: opt.template,
+            layout: Layout {
+                left: opt.left_sections,
+                main: opt.main_sections,
+            },
+        },
+        tags: TagFilter {
+            include: opt.tags,
+            exclude: opt.exclude_tags,
+        },
+        ghostscript: opt.ghostscript,
+        dpi: opt.dpi,
+    };
+    std::process::exit(match generate(job) {
+        Ok(()) => {
+            info!("Resume generated");
+            0
+        }
+
+        Err(err) => {
+            error!("{}", err);
+            1
+        }
+    })
+}
+
+/// Everything needed to generate one document from a resume.
+#[derive(Debug)]
+struct Job {
+    filename: PathBuf,
+    output: PathBuf,
+    format: Format,
+    theme: Option<PathBuf>,
+    fonts: Fonts,
+    options: Options,
+    tags: TagFilter,
+    ghostscript: Option<PathBuf>,
+    dpi: f32,
+}
+
+fn generate(job: Job) -> Result<(), Box<dyn Error>> {
+    let Job {
+        filename,
+        output,
+        format,
+        theme,
+        fonts,
+        options,
+        tags,
+        ghostscript,
+        dpi,
+    } = job;
+    let resume = Resume::from_path(filename)?.filter(&tags);
+    let mut theme = match theme {
+        Some(path) => Theme::from_path(&path)?,
+        None => resume.theme(),
+    };
+    theme.fonts = fonts.or(theme.fonts);
+    let options = Options {
+        layout: options.layout.or(resume.meta.sections.clone()),
+        ..options
+    };
+    match format {
+        Format::Pdf => {
+            let pdf = Pdf::new(resume, theme, options)?;
+            pdf.save(&output)?;
+            optimize_pdf(&output, ghostscript)
+        }
+        Format::Html => {
+            let html = Html::new(
+                resume,
+                theme,
+                options.volunteer_section,
+                options.normalize_institutions,
+            );
+            html.save(&output)
+        }
+        Format::Txt => Txt::new(&resume).save(&output),
+        Format::Markdown => {
+            let markdown = Markdown::new(resume, options.volunteer_section);
+            markdown.save(&output)
+        }
+        Format::Docx => {
+            let docx = Docx::new(resume, theme, options.paper, options.volunteer_section);
+            docx.save(&output)
+        }
+        Format::Latex => Latex::new(resume, theme, options.paper).save(&output),
+        Format::Svg | Format::Png => {
+            let document = Pdf::new(resume, theme, options)?.render()?;
+            let preview = Preview::new(&document, dpi)?;
+            match format {
+                Format::Svg => preview.save_svg(&output),
+                _ => preview.save_png(&output),
+            }
+        }
+    }
 }
 
 fn find_it<P>(exe_name: P) -> Option<PathBuf>
@@ -72,12 +225,12 @@ where
 }
 
 #[cfg(not(target_os = "windows"))]
-fn enhance_exe_name(exe_name: &Path) -> Cow<Path> {
+fn enhance_exe_name(exe_name: &Path) -> Cow<'_, Path> {
     exe_name.into()
 }
 
 #[cfg(target_os = "windows")]
-fn enhance_exe_name(exe_name: &Path) -> Cow<Path> {
+fn enhance_exe_name(exe_name: &Path) -> Cow<'_, Path> {
     use std::ffi::OsStr;
     use std::os::windows::ffi::OsStrExt;
 
@@ -93,6 +246,9 @@ fn enhance_exe_name(exe_name: &Path) -> Cow<Path> {
     }
 }
 
+/// Number of temporary files created for Ghostscript.
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
 fn optimize_pdf(filename: &PathBuf, ghostscript: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
     debug!("Looking for ghostscript");
     match ghostscript
@@ -106,10 +262,17 @@ fn optimize_pdf(filename: &PathBuf, ghostscript: Option<PathBuf>) -> Result<(),
         Some(path) => {
             let canonicalize_filename = canonicalize(filename).unwrap();
             debug!("Ghostscript found, {:?}", path);
+            // Outputs of a manifest may share a file name and be optimized
+            // at the same time
             let mut temp_file = env::temp_dir();
-            temp_file.push(canonicalize_filename.file_name().unwrap());
+            temp_file.push(format!(
+                "{}-{}-{}",
+                std::process::id(),
+                TEMP_FILES.fetch_add(1, Ordering::Relaxed),
+                canonicalize_filename.file_name().unwrap().to_string_lossy()
+            ));
             let temp_file_as_string = temp_file.to_str().unwrap();
-            rename(filename, &temp_file_as_string)?;
+            rename(filename, temp_file_as_string)?;
             debug!(
                 "Running Ghostscript using temp file: {}",
                 &temp_file_as_string
@@ -125,10 +288,10 @@ fn optimize_pdf(filename: &PathBuf, ghostscript: Option<PathBuf>) -> Result<(),
                     "-sOutputFile={}",
                     canonicalize_filename.to_str().unwrap()
                 ))
-                .arg(&temp_file_as_string)
+                .arg(temp_file_as_string)
                 .output()?;
             debug!("Ghostscript {}", output.status);
-            remove_file(&temp_file_as_string)?;
+            remove_file(temp_file_as_string)?;
             Ok(())
         }
     }
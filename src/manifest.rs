@@ -0,0 +1,164 @@
+use crate::format::Format;
+use crate::pdf::{Options, Template};
+use crate::preview::{check_dpi, InvalidDpiError};
+use crate::resume::TagFilter;
+use crate::theme::Fonts;
+use crate::{generate, Job};
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::fs::read_to_string;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+/// Outputs generated in one run by `resume-generator build`, their paths
+/// being relative to the manifest.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "output")]
+    pub outputs: Vec<Output>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Output {
+    /// Resume to generate, (Json or Toml)
+    pub resume: PathBuf,
+    /// Generated resume
+    pub output: PathBuf,
+    /// Guessed from the output extension by default
+    pub format: Option<Format>,
+    #[serde(default)]
+    pub template: Template,
+    /// Theme file, overriding the resume meta.theme section
+    pub theme: Option<PathBuf>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+    /// Resolution of svg and png previews, overriding the one of the command
+    pub dpi: Option<f32>,
+    /// Language of the section titles, only English being available
+    pub language: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct UnsupportedLanguageError {
+    output: PathBuf,
+    language: String,
+}
+
+impl fmt::Display for UnsupportedLanguageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: Unsupported language: {}, section titles only exist in English (en)",
+            self.output.display(),
+            self.language
+        )
+    }
+}
+
+impl Error for UnsupportedLanguageError {}
+
+impl Manifest {
+    pub fn from_path(path: &Path) -> Result<Self, Box<dyn Error>> {
+        debug!("Opening manifest: {:?}", path);
+        let mut manifest: Manifest = toml::from_str(&read_to_string(path)?)?;
+        if let Some(parent) = path.parent() {
+            manifest.outputs.iter_mut().for_each(|output| {
+                output.resume = parent.join(&output.resume);
+                output.output = parent.join(&output.output);
+                output.theme = output.theme.as_ref().map(|theme| parent.join(theme));
+            });
+        }
+        for output in &manifest.outputs {
+            if let Some(language) = &output.language {
+                let primary = language.split(['-', '_']).next().unwrap_or_default();
+                if !primary.eq_ignore_ascii_case("en") {
+                    return Err(Box::new(UnsupportedLanguageError {
+                        output: output.output.clone(),
+                        language: language.clone(),
+                    }));
+                }
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+impl Output {
+    fn job(self, ghostscript: Option<PathBuf>, dpi: f32) -> Result<Job, InvalidDpiError> {
+        let format = self
+            .format
+            .or_else(|| Format::from_path(&self.output))
+            .unwrap_or_default();
+        Ok(Job {
+            filename: self.resume,
+            output: self.output,
+            format,
+            theme: self.theme,
+            fonts: Fonts::default(),
+            options: Options {
+                template: self.template,
+                ..Options::default()
+            },
+            tags: TagFilter {
+                include: self.tags,
+                exclude: self.exclude_tags,
+            },
+            ghostscript,
+            dpi: check_dpi(self.dpi.unwrap_or(dpi))?,
+        })
+    }
+}
+
+/// Generates the outputs of the manifest at `path` on as many threads as
+/// there are processors, reports how each of them went and returns the
+/// number of failures.
+pub fn build(path: &Path, ghostscript: Option<PathBuf>, dpi: f32) -> Result<usize, Box<dyn Error>> {
+    let outputs = Manifest::from_path(path)?.outputs;
+    let workers = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+        .min(outputs.len());
+    let queue = Mutex::new(outputs.into_iter().enumerate());
+    let results = Mutex::new(vec![]);
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let next = queue.lock().unwrap().next();
+                let (index, output) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                let path = output.output.clone();
+                debug!("Generating {:?}", path);
+                // Errors do not cross threads, their message does
+                let result = match output.job(ghostscript.clone(), dpi) {
+                    Ok(job) => generate(job).map_err(|err| err.to_string()),
+                    Err(err) => Err(err.to_string()),
+                };
+                results.lock().unwrap().push((index, path, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _, _)| *index);
+    let mut failures = 0;
+    for (_, path, result) in results {
+        match result {
+            // Shown whatever the verbosity, as the errors are
+            Ok(()) => println!("Generated {}", path.display()),
+            Err(err) => {
+                failures += 1;
+                error!("{}: {}", path.display(), err);
+            }
+        }
+    }
+    Ok(failures)
+}
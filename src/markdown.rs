@@ -0,0 +1,309 @@
+use crate::entry::Entry;
+use crate::resume::{phone_uri, Interest, Language, Reference, Resume, Skill};
+use crate::timeline::*;
+use crate::titles::*;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs::write;
+use std::path::PathBuf;
+
+const COURSES: &str = "Courses";
+
+/// Markdown document with the sections of the PDF, the timeline being in the
+/// same order.
+pub struct Markdown {
+    resume: Resume,
+    volunteer_section: bool,
+}
+
+impl Markdown {
+    pub fn new(resume: Resume, volunteer_section: bool) -> Self {
+        Self {
+            resume,
+            volunteer_section,
+        }
+    }
+
+    pub fn save(&self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
+        debug!("Generating markdown: {:?}", filename);
+        write(filename, self.render()?)?;
+        Ok(())
+    }
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut markdown = String::new();
+        self.write_bio(&mut markdown)?;
+        self.write_info(&mut markdown)?;
+        self.write_social(&mut markdown)?;
+        self.write_languages(&mut markdown)?;
+        self.write_skills(&mut markdown)?;
+        self.write_timeline(&mut markdown)?;
+        write_entries(&mut markdown, PROJECTS, &self.resume.projects)?;
+        write_entries(&mut markdown, AWARDS, &self.resume.awards)?;
+        write_entries(&mut markdown, CERTIFICATES, &self.resume.certificates)?;
+        write_entries(&mut markdown, PUBLICATIONS, &self.resume.publications)?;
+        self.write_interests(&mut markdown)?;
+        self.write_references(&mut markdown)?;
+        Ok(markdown)
+    }
+
+    fn write_bio(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        let basics = &self.resume.basics;
+        writeln!(markdown, "# {}", escape(&basics.name))?;
+        if !basics.label.is_empty() {
+            writeln!(markdown, "\n**{}**", escape(&basics.label))?;
+        }
+        if let Some(summary) = &basics.summary {
+            writeln!(markdown, "\n{}", summary)?;
+        }
+        Ok(())
+    }
+
+    fn write_info(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        let basics = &self.resume.basics;
+        writeln!(markdown, "\n## {}\n", INFO)?;
+        if let Some(email) = &basics.email {
+            writeln!(markdown, "- Email: [{}](mailto:{})", escape(email), email)?;
+        }
+        if let Some(phone) = &basics.phone {
+            writeln!(
+                markdown,
+                "- Phone: [{}]({})",
+                escape(phone),
+                phone_uri(phone)
+            )?;
+        }
+        if let Some(location) = &basics.location {
+            let lines = location.address_lines();
+            if !lines.is_empty() {
+                writeln!(markdown, "- Location: {}", escape(&lines.join(", ")))?;
+            }
+        }
+        if let Some(birthday) = &basics.birthday {
+            writeln!(
+                markdown,
+                "- Birthday: {} ({}yo)",
+                birthday.format("%d %b %Y"),
+                birthday.age()
+            )?;
+        }
+        if let Some(website) = &basics.website {
+            writeln!(markdown, "- Website: <{}>", website)?;
+        }
+        Ok(())
+    }
+
+    fn write_social(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        let profiles = &self.resume.basics.profiles;
+        if profiles.is_empty() {
+            return Ok(());
+        }
+        writeln!(markdown, "\n## {}\n", SOCIALS)?;
+        for profile in profiles {
+            let username = if profile.username.is_empty() {
+                &profile.url
+            } else {
+                &profile.username
+            };
+            if profile.url.is_empty() {
+                writeln!(
+                    markdown,
+                    "- {}: {}",
+                    escape(&profile.network),
+                    escape(username)
+                )?;
+            } else {
+                writeln!(
+                    markdown,
+                    "- {}: [{}]({})",
+                    escape(&profile.network),
+                    escape(username),
+                    profile.url
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_languages(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.languages.is_empty() {
+            return Ok(());
+        }
+        writeln!(markdown, "\n## {}\n", LANGUAGES)?;
+        for Language { language, fluency } in &self.resume.languages {
+            match fluency {
+                Some(fluency) => {
+                    writeln!(markdown, "- **{}**: {}", escape(language), escape(fluency))?
+                }
+                None => writeln!(markdown, "- **{}**", escape(language))?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_skills(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.skills.is_empty() {
+            return Ok(());
+        }
+        writeln!(markdown, "\n## {}\n", SKILLS)?;
+        for Skill {
+            name,
+            level,
+            keywords,
+            ..
+        } in &self.resume.skills
+        {
+            write!(markdown, "- **{}**", escape(name))?;
+            if let Some(level) = level {
+                write!(markdown, " ({})", escape(level))?;
+            }
+            if !keywords.is_empty() {
+                write!(markdown, ": {}", escape(&keywords.join(", ")))?;
+            }
+            writeln!(markdown)?;
+        }
+        Ok(())
+    }
+
+    fn write_timeline(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        let (timeline, volunteering) = Timeline::from_resume(&self.resume, self.volunteer_section);
+        write_events(markdown, TIMELINE, &timeline.events())?;
+        write_events(markdown, VOLUNTEERING, &volunteering.events())
+    }
+
+    fn write_interests(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.interests.is_empty() {
+            return Ok(());
+        }
+        writeln!(markdown, "\n## {}\n", INTERESTS)?;
+        for Interest { name, keywords } in &self.resume.interests {
+            if keywords.is_empty() {
+                writeln!(markdown, "- **{}**", escape(name))?;
+            } else {
+                writeln!(
+                    markdown,
+                    "- **{}**: {}",
+                    escape(name),
+                    escape(&keywords.join(", "))
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_references(&self, markdown: &mut String) -> Result<(), Box<dyn Error>> {
+        if self.resume.references.is_empty() {
+            return Ok(());
+        }
+        writeln!(markdown, "\n## {}", REFERENCES)?;
+        for Reference { name, reference } in &self.resume.references {
+            writeln!(markdown)?;
+            for line in reference.lines() {
+                writeln!(markdown, "> {}", escape(line))?;
+            }
+            writeln!(markdown, ">\n> \u{2014} {}", escape(name))?;
+        }
+        Ok(())
+    }
+}
+
+fn write_events(
+    markdown: &mut String,
+    title: &str,
+    events: &[Event],
+) -> Result<(), Box<dyn Error>> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    writeln!(markdown, "\n## {}", title)?;
+    for event in events {
+        let (institution, location) = event.institution_and_location();
+        let institution = match &event.website {
+            Some(website) => format!("[{}]({})", escape(institution), website),
+            None => escape(institution),
+        };
+        if event.label.is_empty() {
+            writeln!(markdown, "\n### {}\n", institution)?;
+        } else {
+            writeln!(
+                markdown,
+                "\n### {}, {}\n",
+                escape(&event.label),
+                institution
+            )?;
+        }
+
+        let period = format_period(&event.start_date, event.end_date.as_ref());
+        let location = location.trim();
+        if location.is_empty() {
+            writeln!(markdown, "*{}*", escape(&period))?;
+        } else {
+            writeln!(
+                markdown,
+                "*{} \u{b7} {}*",
+                escape(location),
+                escape(&period)
+            )?;
+        }
+        if let Some(summary) = &event.summary {
+            writeln!(markdown, "\n{}", summary)?;
+        }
+        if !event.highlights.is_empty() {
+            if let EventType::Education = event.event_type {
+                writeln!(markdown, "\n**{}**", COURSES)?;
+            }
+            write_highlights(markdown, &event.highlights)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_entries<T>(markdown: &mut String, title: &str, items: &[T]) -> Result<(), Box<dyn Error>>
+where
+    T: Clone + Into<Entry>,
+{
+    if items.is_empty() {
+        return Ok(());
+    }
+    writeln!(markdown, "\n## {}", title)?;
+    for entry in items.iter().cloned().map(Into::<Entry>::into) {
+        match &entry.website {
+            Some(website) => writeln!(markdown, "\n### [{}]({})", escape(&entry.title), website)?,
+            None => writeln!(markdown, "\n### {}", escape(&entry.title))?,
+        }
+        if !entry.subtitle.is_empty() {
+            writeln!(markdown, "\n*{}*", escape(&entry.subtitle))?;
+        }
+        if let Some(summary) = &entry.summary {
+            writeln!(markdown, "\n{}", summary)?;
+        }
+        write_highlights(markdown, &entry.highlights)?;
+    }
+    Ok(())
+}
+
+fn write_highlights(markdown: &mut String, highlights: &[String]) -> Result<(), Box<dyn Error>> {
+    if !highlights.is_empty() {
+        writeln!(markdown)?;
+        for highlight in highlights {
+            writeln!(markdown, "- {}", highlight)?;
+        }
+    }
+    Ok(())
+}
+
+/// Escapes the characters Markdown would read as formatting in plain fields,
+/// summaries and highlights being written as Markdown on purpose.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    text.chars().for_each(|c| {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '~' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    });
+    escaped
+}
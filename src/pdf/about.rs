@@ -0,0 +1,111 @@
+use super::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Column holding the summary of the resume basics.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AboutPlacement {
+    /// Under the name and label
+    #[default]
+    Left,
+    /// Above the timeline
+    Right,
+}
+
+#[derive(Debug)]
+pub struct UnknownPlacementError {
+    placement: String,
+}
+
+impl fmt::Display for UnknownPlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown placement: {}, expected left or right",
+            self.placement
+        )
+    }
+}
+
+impl Error for UnknownPlacementError {}
+
+impl FromStr for AboutPlacement {
+    type Err = UnknownPlacementError;
+
+    fn from_str(placement: &str) -> Result<Self, Self::Err> {
+        match placement.to_lowercase().as_str() {
+            "left" => Ok(AboutPlacement::Left),
+            "right" => Ok(AboutPlacement::Right),
+            _ => Err(UnknownPlacementError {
+                placement: placement.to_string(),
+            }),
+        }
+    }
+}
+
+impl Pdf {
+    /// Writes the summary as a section of the left column starting at
+    /// `pos_y`.
+    pub(super) fn write_about_left(&mut self, pos_y: Pt) {
+        let summary = match self.resume.basics.summary.clone() {
+            Some(summary) => summary,
+            None => return,
+        };
+        let line_heights = self.theme.line_heights.clone();
+        let sizes = self.theme.font_sizes.clone();
+        let offset_x = self.padding();
+        let width = self.left_text_width();
+        let baseline = pos_y - Pt(sizes.heading) - Pt(line_heights.heading) - Pt(line_heights.text);
+
+        self.write_left_title(ABOUT, pos_y);
+        self.layer.begin_text_section();
+        self.layer.set_line_height(line_heights.text);
+        self.layer.set_text_cursor(offset_x, baseline.into());
+        self.layer.set_font(&self.font_regular, sizes.small);
+        let links = self.write_markdown(
+            &summary,
+            (offset_x.into(), baseline),
+            width,
+            &self.font_regular,
+            sizes.small,
+        );
+        self.layer.end_text_section();
+        self.links.extend(links);
+    }
+
+    /// Writes the summary as a section of the main column and returns the
+    /// position right after it.
+    pub(super) fn write_about(&mut self, pos_y: Pt) -> Pt {
+        let summary = match self.resume.basics.summary.clone() {
+            Some(summary) => summary,
+            None => return pos_y,
+        };
+        let font_size = self.theme.font_sizes.small;
+        let line_height = Pt(self.theme.line_heights.text);
+        let width = self.right_column_width();
+
+        let pos_y = self.write_section_title(ABOUT, pos_y);
+        let lines = self.markdown_lines(&summary, width, &self.font_regular, font_size);
+        let height = line_height * lines as f64;
+        let pos_y = self.reserve(pos_y, height);
+        let offset_x: Pt = self.right_column_offset().into();
+        let baseline = pos_y - Pt(font_size);
+
+        self.layer.set_fill_color(self.primary_color.clone());
+        self.layer.begin_text_section();
+        self.layer.set_line_height(line_height.0);
+        self.layer.set_text_cursor(offset_x.into(), baseline.into());
+        self.layer.set_font(&self.font_regular, font_size);
+        let links = self.write_markdown(
+            &summary,
+            (offset_x, baseline),
+            width,
+            &self.font_regular,
+            font_size,
+        );
+        self.layer.end_text_section();
+        self.links.extend(links);
+
+        pos_y - height - Pt(self.theme.spacing.entry * 2.)
+    }
+}
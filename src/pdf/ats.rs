@@ -0,0 +1,96 @@
+use super::text::wrapped_lines;
+use super::*;
+use crate::ats::{self, Block};
+
+const ITEM_PREFIX: &str = "- ";
+
+impl Pdf {
+    /// Writes the resume as a single column without icons, picture nor QR
+    /// code, in the reading order of the text format.
+    pub(super) fn write_ats(&mut self) {
+        let offset_x = self.padding();
+        let width = self.page_width() - self.padding() - self.padding();
+        let sizes = self.theme.font_sizes.clone();
+        let line_heights = self.theme.line_heights.clone();
+        let mut pos_y = self.content_top();
+
+        ats::blocks(&self.resume).iter().for_each(|block| {
+            // Summaries and highlights may hold inline Markdown
+            let markdown = matches!(block, Block::Text(_) | Block::Item(_));
+            let (text, bold, font_size, line_height) = match block {
+                Block::Name(name) => (name.to_uppercase(), true, sizes.name, line_heights.name),
+                Block::Heading(heading) => {
+                    pos_y = self.write_ats_heading(heading, pos_y);
+                    return;
+                }
+                Block::Title(title) => (title.clone(), true, sizes.text, line_heights.text),
+                Block::Text(text) => (text.clone(), false, sizes.small, line_heights.text),
+                Block::Item(item) => (
+                    format!("{}{}", ITEM_PREFIX, item),
+                    false,
+                    sizes.small,
+                    line_heights.text,
+                ),
+                Block::Break => {
+                    pos_y -= Pt(self.theme.spacing.entry);
+                    return;
+                }
+            };
+            let font = if bold {
+                self.font_bold.clone()
+            } else {
+                self.font_regular.clone()
+            };
+            let font = &font;
+            let lines = if markdown {
+                self.markdown_lines(&text, width, font, font_size)
+            } else {
+                wrapped_lines(&text, width, font, font_size)
+            };
+            // The first baseline lies a font size down, and the name is
+            // followed by a whole line to set it apart from the label
+            let height = match block {
+                Block::Name(_) => Pt(font_size) + Pt(line_height) * lines as f64,
+                _ => Pt(font_size.max(line_height)) + Pt(line_height) * (lines - 1) as f64,
+            };
+            pos_y = self.reserve(pos_y, height);
+
+            self.layer.set_fill_color(self.primary_color.clone());
+            self.layer.begin_text_section();
+            self.layer.set_line_height(line_height);
+            let baseline = pos_y - Pt(font_size);
+            self.layer.set_text_cursor(offset_x, baseline.into());
+            self.layer.set_font(font, font_size);
+            let mut links = vec![];
+            if markdown {
+                links =
+                    self.write_markdown(&text, (offset_x.into(), baseline), width, font, font_size);
+            } else {
+                self.write_bounded(&text, width, font, font_size);
+            }
+            self.layer.end_text_section();
+            self.links.extend(links);
+            pos_y -= height;
+        });
+    }
+
+    fn write_ats_heading(&mut self, heading: &str, pos_y: Pt) -> Pt {
+        let font_size = self.theme.font_sizes.heading;
+        let height = Pt(self.theme.spacing.entry * 2.) + Pt(font_size);
+        let pos_y = self.reserve(
+            pos_y - Pt(self.theme.spacing.entry),
+            height + Pt(self.theme.line_heights.text),
+        );
+        self.layer.set_fill_color(self.primary_color.clone());
+        self.layer.begin_text_section();
+        self.write_underlined_text(
+            heading,
+            font_size,
+            self.padding(),
+            (pos_y - Pt(font_size)).into(),
+            self.page_width() - self.padding() - self.padding(),
+        );
+        self.layer.end_text_section();
+        pos_y - height
+    }
+}
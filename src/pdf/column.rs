@@ -0,0 +1,231 @@
+use super::text::wrapped_lines;
+use super::*;
+use crate::resume::Profile;
+
+/// Written between the items of a contact line.
+const SEPARATOR: &str = "  |  ";
+
+/// Text of an item of a contact line and the link it opens.
+type Item = (String, Option<String>);
+
+impl Pdf {
+    /// Writes the name and label across the main column and returns the
+    /// position right after them.
+    pub(super) fn write_header(&mut self, pos_y: Pt) -> Pt {
+        let sizes = self.theme.font_sizes.clone();
+        let line_heights = self.theme.line_heights.clone();
+        let width = self.right_column_width();
+        let name = self.resume.basics.name.clone();
+        let name_lines = wrapped_lines(&name, width, &self.font_bold, sizes.name);
+        let height =
+            Pt(sizes.name) + Pt(line_heights.name) * name_lines as f64 + Pt(line_heights.text / 2.);
+        let pos_y = self.reserve(pos_y, height);
+
+        self.layer.set_fill_color(self.primary_color.clone());
+        self.layer.begin_text_section();
+        self.layer
+            .set_text_cursor(self.right_column_offset(), (pos_y - Pt(sizes.name)).into());
+        self.layer.set_line_height(line_heights.name);
+        self.layer.set_font(&self.font_bold, sizes.name);
+        self.write_bounded(&name, width, &self.font_bold, sizes.name);
+        self.layer.set_font(&self.font_regular, sizes.text);
+        self.layer
+            .write_text(&self.resume.basics.label, &self.font_regular);
+        self.layer.end_text_section();
+
+        pos_y - height
+    }
+
+    /// Writes the contact details on a line, wrapping as needed.
+    pub(super) fn write_contact_line(&mut self, pos_y: Pt) -> Pt {
+        let basics = &self.resume.basics;
+        let mut items: Vec<Item> = vec![];
+        if let Some(email) = &basics.email {
+            items.push((email.clone(), Some(format!("mailto:{}", email))));
+        }
+        if let Some(phone) = &basics.phone {
+            items.push((phone.clone(), Some(phone_uri(phone))));
+        }
+        if let Some(Location {
+            city: Some(city),
+            country_code,
+            ..
+        }) = &basics.location
+        {
+            let location = match country_code {
+                Some(country_code) => format!("{}, {}", city, country_code),
+                None => city.clone(),
+            };
+            items.push((location, None));
+        }
+        if let Some(website) = &basics.website {
+            items.push((website.clone(), Some(website.clone())));
+        }
+        if let Some(birthday) = &basics.birthday {
+            let text = format!("{} ({}yo)", birthday.format("%d %b %Y"), birthday.age());
+            items.push((text, None));
+        }
+        self.write_items(&items, pos_y)
+    }
+
+    /// Writes the social profiles on a line, wrapping as needed.
+    pub(super) fn write_profile_line(&mut self, pos_y: Pt) -> Pt {
+        let items: Vec<Item> = self
+            .resume
+            .basics
+            .profiles
+            .iter()
+            .map(
+                |Profile {
+                     network,
+                     username,
+                     url,
+                 }| {
+                    let link = Some(url.clone()).filter(|url| !url.is_empty());
+                    (format!("{}: {}", network, username), link)
+                },
+            )
+            .collect();
+        self.write_items(&items, pos_y)
+    }
+
+    /// Writes the languages and their fluency as highlights.
+    pub(super) fn write_language_list(&mut self, pos_y: Pt) -> Pt {
+        let languages: Vec<String> = self
+            .resume
+            .languages
+            .iter()
+            .map(|Language { language, fluency }| match fluency {
+                Some(fluency) => format!("**{}** ({})", language, fluency),
+                None => format!("**{}**", language),
+            })
+            .collect();
+        if languages.is_empty() {
+            return pos_y;
+        }
+        let pos_y = self.write_section_title(LANGUAGES, pos_y);
+        let font_size = self.theme.font_sizes.small;
+        let width = self.right_column_width();
+        let height = self.highlights_height(&languages, width, &self.font_regular, font_size);
+        let pos_y = self.reserve(pos_y, height);
+
+        let colors = (self.primary_color.clone(), self.secondary_color.clone());
+        let links = self.write_highlights(
+            &languages,
+            (self.right_column_offset().into(), pos_y - Pt(font_size)),
+            width,
+            &self.font_regular,
+            font_size,
+            &colors,
+        );
+        self.links.extend(links);
+        pos_y - height - Pt(self.theme.spacing.entry)
+    }
+
+    /// Writes each skill with its level, followed by its keywords.
+    pub(super) fn write_skill_list(&mut self, pos_y: Pt) -> Pt {
+        if self.resume.skills.is_empty() {
+            return pos_y;
+        }
+        let skills: Vec<Skill> = self.resume.skills.to_vec();
+        let mut pos_y = self.write_section_title(SKILLS, pos_y);
+        let sizes = self.theme.font_sizes.clone();
+        let line_height = Pt(self.theme.line_heights.text);
+        let width = self.right_column_width();
+        let offset_x = self.right_column_offset();
+        let colors = (self.primary_color.clone(), self.secondary_color.clone());
+
+        skills.iter().for_each(
+            |Skill {
+                 name,
+                 level,
+                 keywords,
+                 ..
+             }| {
+                let height = line_height
+                    + self.highlights_height(keywords, width, &self.font_regular, sizes.small);
+                pos_y = self.reserve(pos_y, height);
+
+                self.layer.set_fill_color(self.primary_color.clone());
+                self.layer.begin_text_section();
+                self.layer
+                    .set_text_cursor(offset_x, (pos_y - Pt(sizes.text)).into());
+                self.layer.set_font(&self.font_bold, sizes.text);
+                self.layer.write_text(name, &self.font_bold);
+                if let Some(level) = level {
+                    self.layer.set_font(&self.font_thin, sizes.text);
+                    self.layer
+                        .write_text(format!("  ({})", level), &self.font_thin);
+                }
+                self.layer.end_text_section();
+
+                let links = self.write_highlights(
+                    keywords,
+                    (offset_x.into(), pos_y - Pt(sizes.text) - line_height),
+                    width,
+                    &self.font_regular,
+                    sizes.small,
+                    &colors,
+                );
+                self.links.extend(links);
+                pos_y -= height + Pt(self.theme.spacing.entry);
+            },
+        );
+        pos_y
+    }
+
+    /// Writes `items` one after the other, separated and wrapped to the
+    /// width of the main column, making the linked ones clickable.
+    fn write_items(&mut self, items: &[Item], pos_y: Pt) -> Pt {
+        if items.is_empty() {
+            return pos_y;
+        }
+        let font_size = self.theme.font_sizes.small;
+        let line_height = Pt(self.theme.line_heights.text);
+        let left: Pt = self.right_column_offset().into();
+        let right = left + self.right_column_width().into();
+        let separator_width = self.font_regular.text_width(SEPARATOR, font_size);
+
+        let mut rows: Vec<Vec<&Item>> = vec![];
+        let mut pos_x = left;
+        items.iter().for_each(|item| {
+            let width = self.font_regular.text_width(&item.0, font_size);
+            match rows.last_mut() {
+                Some(row) if pos_x + separator_width + width <= right => {
+                    pos_x += separator_width + width;
+                    row.push(item);
+                }
+                _ => {
+                    pos_x = left + width;
+                    rows.push(vec![item]);
+                }
+            }
+        });
+
+        let mut pos_y = pos_y;
+        for row in rows {
+            pos_y = self.reserve(pos_y, line_height);
+            let baseline = pos_y - Pt(font_size);
+            let mut pos_x = left;
+            self.layer.set_fill_color(self.primary_color.clone());
+            self.layer.begin_text_section();
+            self.layer.set_font(&self.font_regular, font_size);
+            self.layer.set_text_cursor(left.into(), baseline.into());
+            for (index, (text, link)) in row.into_iter().enumerate() {
+                if index > 0 {
+                    self.layer.write_text(SEPARATOR, &self.font_regular);
+                    pos_x += separator_width;
+                }
+                self.layer.write_text(text, &self.font_regular);
+                let width = self.font_regular.text_width(text, font_size);
+                if let Some(uri) = link {
+                    self.link_text(uri.clone(), (pos_x, baseline), width, 1, font_size);
+                }
+                pos_x += width;
+            }
+            self.layer.end_text_section();
+            pos_y -= line_height;
+        }
+        pos_y - Pt(self.theme.line_heights.text / 2.)
+    }
+}
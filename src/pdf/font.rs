@@ -0,0 +1,85 @@
+use owned_ttf_parser::{AsFaceRef, GlyphId, OwnedFace};
+use printpdf::{IndirectFontRef, PdfDocumentReference, Pt};
+use std::error::Error;
+use std::fmt;
+use std::fs::read;
+use std::io::Cursor;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Font added to the document, along with its metrics. Clones share the
+/// embedded font.
+#[derive(Clone)]
+pub struct Font {
+    reference: IndirectFontRef,
+    face: Rc<OwnedFace>,
+}
+
+#[derive(Debug)]
+pub enum FontError {
+    Missing(PathBuf, std::io::Error),
+    Invalid(PathBuf, printpdf::Error),
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FontError::Missing(path, err) => write!(f, "Cannot read font {:?}: {}", path, err),
+            FontError::Invalid(path, err) => {
+                write!(f, "{:?} is not a valid TTF/OTF font: {}", path, err)
+            }
+        }
+    }
+}
+
+impl Error for FontError {}
+
+impl Font {
+    /// Width of `text` once written at `font_size`.
+    pub fn text_width(&self, text: &str, font_size: f64) -> Pt {
+        let face = self.face.as_face_ref();
+        let units_per_em = f64::from(face.units_per_em().unwrap_or(1000));
+        let units: u32 = text
+            .chars()
+            .map(|c| {
+                let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+                u32::from(face.glyph_hor_advance(glyph).unwrap_or(0))
+            })
+            .sum();
+        Pt(f64::from(units) * font_size / units_per_em)
+    }
+}
+
+impl Deref for Font {
+    type Target = IndirectFontRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.reference
+    }
+}
+
+/// Adds the font at `path` to the document, or the `embedded` one when no
+/// path is given.
+pub fn load(
+    doc: &PdfDocumentReference,
+    path: Option<&Path>,
+    embedded: &'static [u8],
+) -> Result<Font, Box<dyn Error>> {
+    let (reference, bytes) = match path {
+        None => (
+            doc.add_external_font(Cursor::new(embedded))?,
+            embedded.to_vec(),
+        ),
+        Some(path) => {
+            debug!("Loading font: {:?}", path);
+            let bytes = read(path).map_err(|err| FontError::Missing(path.to_path_buf(), err))?;
+            let font = doc
+                .add_external_font(Cursor::new(&bytes))
+                .map_err(|err| FontError::Invalid(path.to_path_buf(), err))?;
+            (font, bytes)
+        }
+    };
+    let face = Rc::new(OwnedFace::from_vec(bytes, 0)?);
+    Ok(Font { reference, face })
+}
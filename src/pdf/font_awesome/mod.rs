@@ -28,7 +28,7 @@ impl Error for UnkownFontError {
         Box::leak(self.to_string().into_boxed_str())
     }
 
-    fn cause(&self) -> Option<&(dyn Error)> {
+    fn cause(&self) -> Option<&dyn Error> {
         None
     }
 }
@@ -45,13 +45,13 @@ impl FontAwesome {
         &self,
         current_layer: PdfLayerReference,
         name: &str,
-        font_size: i64,
+        font_size: f64,
         color: Color,
     ) -> Result<(), Box<dyn Error>> {
         let icon = FONTS.get(name).ok_or_else(|| UnkownFontError::new(name))?;
         let font = &self.regular;
         current_layer.set_fill_color(color);
-        current_layer.set_font(font, font_size as f64);
+        current_layer.set_font(font, font_size);
         current_layer.write_text(*icon, font);
         Ok(())
     }
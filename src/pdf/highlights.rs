@@ -0,0 +1,163 @@
+use super::font::Font;
+use super::inline;
+use super::link::Link;
+use super::sections::{chip_height, CHIP_PADDING, CHIP_SPACING};
+use super::text::wrap;
+use super::*;
+use crate::theme::HighlightStyle;
+
+/// Baseline of the text of a chip, from its top.
+const CHIP_BASELINE: Pt = Pt(10.);
+
+/// Text of a chip, its lines separated by line breaks, the link it opens and
+/// its width.
+pub(super) type Chip = (String, Option<String>, Pt);
+
+impl Pdf {
+    /// Height taken by the highlights once written `width` wide, as a bullet
+    /// list or as chips depending on the theme.
+    pub(super) fn highlights_height(
+        &self,
+        highlights: &[String],
+        width: Mm,
+        font: &Font,
+        font_size: f64,
+    ) -> Pt {
+        match self.theme.highlights.style {
+            HighlightStyle::List => {
+                let width = width - self.bullet_indent(font, font_size).into();
+                let lines: usize = highlights
+                    .iter()
+                    .map(|highlight| self.markdown_lines(highlight, width, font, font_size))
+                    .sum();
+                Pt(self.theme.line_heights.text) * lines as f64
+            }
+            HighlightStyle::Chips => self
+                .chip_rows(highlights, width)
+                .iter()
+                .fold(Pt(0.), |height, row| {
+                    height + row_height(row) + CHIP_SPACING
+                }),
+        }
+    }
+
+    /// Writes the highlights as a bullet list with a hanging indent, or as
+    /// chips, and returns their links. `origin` is the start of the first
+    /// baseline and `colors` the text color and the background one.
+    pub(super) fn write_highlights(
+        &self,
+        highlights: &[String],
+        origin: (Pt, Pt),
+        width: Mm,
+        font: &Font,
+        font_size: f64,
+        colors: &(Color, Color),
+    ) -> Vec<Link> {
+        let (offset_x, mut baseline) = origin;
+        let mut links = vec![];
+        match self.theme.highlights.style {
+            HighlightStyle::List => {
+                let indent = self.bullet_indent(font, font_size);
+                let width = width - indent.into();
+                let line_height = Pt(self.theme.line_heights.text);
+                self.layer.set_fill_color(colors.0.clone());
+                highlights.iter().for_each(|highlight| {
+                    self.layer.begin_text_section();
+                    self.layer.set_font(font, font_size);
+                    self.layer.set_text_cursor(offset_x.into(), baseline.into());
+                    self.layer.write_text(&self.theme.highlights.bullet, font);
+                    self.layer.end_text_section();
+
+                    self.layer.begin_text_section();
+                    self.layer.set_line_height(line_height.0);
+                    self.layer
+                        .set_text_cursor((offset_x + indent).into(), baseline.into());
+                    self.layer.set_font(font, font_size);
+                    links.extend(self.write_markdown(
+                        highlight,
+                        (offset_x + indent, baseline),
+                        width,
+                        font,
+                        font_size,
+                    ));
+                    self.layer.end_text_section();
+                    baseline -=
+                        line_height * self.markdown_lines(highlight, width, font, font_size) as f64;
+                });
+            }
+            HighlightStyle::Chips => {
+                let mut top = baseline + CHIP_BASELINE;
+                self.chip_rows(highlights, width).iter().for_each(|row| {
+                    let mut pos_x = offset_x;
+                    row.iter().for_each(|(text, link, chip_width)| {
+                        self.write_chip(text, false, pos_x, top, *chip_width, colors);
+                        if let Some(uri) = link {
+                            links.push(Link {
+                                page: self.page,
+                                lower_left: (pos_x, top - chip_height(text)),
+                                upper_right: (pos_x + *chip_width, top),
+                                uri: uri.clone(),
+                            });
+                        }
+                        pos_x += *chip_width + CHIP_SPACING;
+                    });
+                    top -= row_height(row) + CHIP_SPACING;
+                });
+            }
+        }
+        links
+    }
+
+    /// Width of the bullet and of the space after it.
+    fn bullet_indent(&self, font: &Font, font_size: f64) -> Pt {
+        font.text_width(&format!("{} ", self.theme.highlights.bullet), font_size)
+    }
+
+    /// Distributes the highlights over rows of chips no wider than `width`,
+    /// their inline Markdown being reduced to its text and first link. Chips
+    /// too wide for a row of their own wrap over several lines.
+    pub(super) fn chip_rows(&self, highlights: &[String], width: Mm) -> Vec<Vec<Chip>> {
+        let text_width = width - (CHIP_PADDING + CHIP_PADDING).into();
+        let width: Pt = width.into();
+        let font_size = self.theme.font_sizes.small;
+        let mut rows: Vec<Vec<Chip>> = vec![];
+        let mut row_width = Pt(0.);
+        highlights.iter().for_each(|highlight| {
+            let spans = inline::parse(highlight);
+            let text: String = spans.iter().map(|span| span.text.as_str()).collect();
+            let link = spans.iter().find_map(|span| span.link.clone());
+            let lines = wrap(&text, text_width, &self.font_regular, font_size);
+            let text = lines.join("\n");
+            let chip_width = lines
+                .iter()
+                .map(|line| self.font_regular.text_width(line, font_size))
+                .fold(
+                    Pt(0.),
+                    |widest, width| if width > widest { width } else { widest },
+                )
+                + CHIP_PADDING
+                + CHIP_PADDING;
+            match rows.last_mut() {
+                Some(row) if row_width + CHIP_SPACING + chip_width <= width => {
+                    row_width += CHIP_SPACING + chip_width;
+                    row.push((text, link, chip_width));
+                }
+                _ => {
+                    row_width = chip_width;
+                    rows.push(vec![(text, link, chip_width)]);
+                }
+            }
+        });
+        rows
+    }
+}
+
+/// Height of the tallest chip of `row`.
+pub(super) fn row_height(row: &[Chip]) -> Pt {
+    row.iter()
+        .map(|(text, _, _)| chip_height(text))
+        .fold(
+            Pt(0.),
+            |tallest, height| if height > tallest { height } else { tallest },
+        )
+}
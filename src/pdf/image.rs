@@ -16,7 +16,7 @@ pub fn qrcode(url: &str, size: usize, color: Rgb) -> Result<Image, image_crate::
     ];
     for pixel in buffer.pixels_mut() {
         if pixel.0 == [0, 0, 0] {
-            (*pixel).0 = new_color;
+            pixel.0 = new_color;
         }
     }
     let dyn_image = image_crate::DynamicImage::ImageRgb8(buffer);
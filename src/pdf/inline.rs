@@ -0,0 +1,177 @@
+/// Run of text sharing the same emphasis and link.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub link: Option<String>,
+}
+
+/// Splits the inline Markdown of `text` into spans: `**bold**`, `__bold__`,
+/// `*italic*`, `_italic_`, `[text](url)` and backslash escapes. Markers
+/// without a closing counterpart are kept as is.
+pub fn parse(text: &str) -> Vec<Span> {
+    let mut spans = vec![];
+    parse_into(text, &Span::default(), &mut spans);
+    spans
+}
+
+fn parse_into(text: &str, style: &Span, spans: &mut Vec<Span>) {
+    let mut plain = String::new();
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let rest = &text[i..];
+        let previous = text[..i].chars().next_back();
+
+        if c == '\\' {
+            if let Some(escaped) = rest[1..].chars().next().filter(char::is_ascii_punctuation) {
+                plain.push(escaped);
+                i += 2;
+                continue;
+            }
+        }
+
+        if let Some((marker, inner, end)) = emphasis(rest, previous) {
+            flush(&mut plain, style, spans);
+            let inner_style = Span {
+                bold: style.bold || marker.len() == 2,
+                italic: style.italic || marker.len() == 1,
+                ..style.clone()
+            };
+            parse_into(inner, &inner_style, spans);
+            i += end;
+            continue;
+        }
+
+        if let Some((label, url, end)) = link(rest) {
+            flush(&mut plain, style, spans);
+            let link_style = Span {
+                link: Some(url.to_string()),
+                ..style.clone()
+            };
+            parse_into(label, &link_style, spans);
+            i += end;
+            continue;
+        }
+
+        plain.push(c);
+        i += c.len_utf8();
+    }
+    flush(&mut plain, style, spans);
+}
+
+fn flush(plain: &mut String, style: &Span, spans: &mut Vec<Span>) {
+    if !plain.is_empty() {
+        spans.push(Span {
+            text: std::mem::take(plain),
+            ..style.clone()
+        });
+    }
+}
+
+/// Emphasis starting `rest`: its marker, its content and the length up to
+/// the end of the closing marker.
+fn emphasis(rest: &str, previous: Option<char>) -> Option<(&str, &str, usize)> {
+    let marker = ["**", "__", "*", "_"]
+        .iter()
+        .find(|marker| rest.starts_with(*marker))?;
+    let underscore = marker.starts_with('_');
+    // Underscores inside words, as in snake_case, are not emphasis
+    if underscore && previous.is_some_and(char::is_alphanumeric) {
+        return None;
+    }
+    let content = &rest[marker.len()..];
+    if content.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let mut search = 0;
+    while let Some(found) = content[search..].find(marker) {
+        let close = search + found;
+        let after = &content[close + marker.len()..];
+        let closes = close > 0
+            && !content[..close].ends_with(char::is_whitespace)
+            // A single marker is not the half of a double one
+            && !(marker.len() == 1 && (after.starts_with(marker) || content[..close].ends_with(marker)))
+            && !(underscore && after.starts_with(char::is_alphanumeric));
+        if closes {
+            return Some((marker, &content[..close], marker.len() * 2 + close));
+        }
+        search = close + marker.len();
+    }
+    None
+}
+
+/// Link starting `rest`: its label, its url and its length.
+fn link(rest: &str) -> Option<(&str, &str, usize)> {
+    if !rest.starts_with('[') {
+        return None;
+    }
+    let label_end = rest.find("](")?;
+    let url_end = label_end + 2 + rest[label_end + 2..].find(')')?;
+    let label = &rest[1..label_end];
+    let url = rest[label_end + 2..url_end].trim();
+    if label.is_empty() || url.is_empty() || label.contains('[') {
+        return None;
+    }
+    Some((label, url, url_end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> Span {
+        Span {
+            text: text.to_string(),
+            ..Span::default()
+        }
+    }
+
+    #[test]
+    fn parses_emphasis() {
+        assert_eq!(
+            parse("a **bold** and _italic_ word"),
+            vec![
+                plain("a "),
+                Span {
+                    bold: true,
+                    ..plain("bold")
+                },
+                plain(" and "),
+                Span {
+                    italic: true,
+                    ..plain("italic")
+                },
+                plain(" word"),
+            ]
+        );
+    }
+
+    #[test]
+    fn nests_emphasis_in_links() {
+        assert_eq!(
+            parse("see [**my** blog](https://blog.example.com)"),
+            vec![
+                plain("see "),
+                Span {
+                    bold: true,
+                    link: Some("https://blog.example.com".to_string()),
+                    ..plain("my")
+                },
+                Span {
+                    link: Some("https://blog.example.com".to_string()),
+                    ..plain(" blog")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_unmatched_markers_and_escapes() {
+        assert_eq!(parse("snake_case * 2"), vec![plain("snake_case * 2")]);
+        assert_eq!(parse("2 ** 3 **"), vec![plain("2 ** 3 **")]);
+        assert_eq!(parse("\\*not italic\\*"), vec![plain("*not italic*")]);
+        assert_eq!(parse("[no link]"), vec![plain("[no link]")]);
+    }
+}
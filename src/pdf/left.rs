@@ -0,0 +1,388 @@
+use super::text::wrapped_lines;
+use super::*;
+
+/// Indent of the text following the icon of a row.
+const ICON_INDENT: Mm = Mm(7.);
+
+/// Icon, text and link of a row of the info and social sections.
+type Row = (String, String, Option<String>);
+
+impl Pdf {
+    /// Writes the sections one below the other from `pos_y`, continuing on
+    /// the next page those running into `bottom`. The skills continue skill
+    /// by skill, the other sections as a whole.
+    pub(super) fn write_left_sections(
+        &mut self,
+        sections: &[Section],
+        pos_y: Pt,
+        bottom: Pt,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut pos_y = pos_y;
+        let mut bottom = bottom;
+        for section in sections {
+            let height = self.left_section_height(*section);
+            if height == Pt(0.) {
+                continue;
+            }
+            let needed = match section {
+                Section::Skills => {
+                    self.left_title_height() + self.skill_height(&self.resume.skills[0])
+                }
+                _ => height,
+            };
+            if pos_y - needed < bottom && pos_y < self.content_top() {
+                self.continue_left_column();
+                pos_y = self.content_top();
+                bottom = self.page_margin();
+            }
+            if pos_y - needed < bottom {
+                warn!(
+                    "{:?} is taller than the left column, running past its bottom",
+                    section
+                );
+            }
+            if *section == Section::Skills {
+                pos_y = self.write_skills(pos_y, &mut bottom);
+            } else {
+                match section {
+                    Section::Bio => self.write_bio(pos_y),
+                    Section::About => self.write_about_left(pos_y),
+                    Section::Info => self.write_info(pos_y)?,
+                    Section::Social => self.write_social(pos_y)?,
+                    Section::Languages => self.write_languages(pos_y),
+                    _ => (),
+                }
+                pos_y -= height;
+            }
+            pos_y -= Pt(self.theme.spacing.section / 2.);
+        }
+        Ok(())
+    }
+
+    /// Height taken by `section` in the left column, nil when there is
+    /// nothing to show.
+    fn left_section_height(&self, section: Section) -> Pt {
+        let sizes = &self.theme.font_sizes;
+        let line_heights = &self.theme.line_heights;
+        let title = self.left_title_height();
+        let basics = &self.resume.basics;
+        match section {
+            Section::Bio => {
+                let name_lines = basics.name.split_whitespace().count();
+                Pt(sizes.name) + Pt(line_heights.name) * name_lines as f64
+            }
+            Section::About => match &basics.summary {
+                Some(summary) => {
+                    let lines = self.markdown_lines(
+                        summary,
+                        self.left_text_width(),
+                        &self.font_regular,
+                        sizes.small,
+                    );
+                    title + Pt(line_heights.text) * lines as f64
+                }
+                None => Pt(0.),
+            },
+            Section::Info => self.left_rows_height(&self.info_rows(), sizes.small),
+            Section::Social => self.left_rows_height(&self.social_rows(), sizes.text),
+            Section::Languages => match self.resume.languages.len() {
+                0 => Pt(0.),
+                languages => title + Pt(line_heights.list) * languages as f64,
+            },
+            Section::Skills if self.resume.skills.is_empty() => Pt(0.),
+            Section::Skills => self
+                .resume
+                .skills
+                .iter()
+                .fold(title, |height, skill| height + self.skill_height(skill)),
+            // Ruled out by `Layout::check`
+            _ => Pt(0.),
+        }
+    }
+
+    /// Moves the left column to the next page, drawing its background when
+    /// continuation pages leave it out.
+    fn continue_left_column(&mut self) {
+        self.add_page();
+        if self.options.omit_left_column {
+            self.draw_left_background();
+        }
+    }
+
+    fn write_bio(&self, pos_y: Pt) {
+        let sizes = &self.theme.font_sizes;
+        self.layer.set_fill_color(self.secondary_color.clone());
+
+        self.layer.begin_text_section();
+        self.layer.set_font(&self.font_bold, sizes.name);
+        self.layer
+            .set_text_cursor(self.padding(), (pos_y - Pt(sizes.name)).into());
+        self.layer.set_line_height(self.theme.line_heights.name);
+        self.resume
+            .basics
+            .name
+            .to_uppercase()
+            .split_whitespace()
+            .for_each(|part| {
+                self.layer.write_text(part, &self.font_bold);
+                self.layer.add_line_break();
+            });
+
+        self.layer.set_font(&self.font_regular, sizes.text);
+        self.layer
+            .write_text(&self.resume.basics.label, &self.font_regular);
+        self.layer.end_text_section();
+    }
+
+    fn write_info(&mut self, pos_y: Pt) -> Result<(), Box<dyn Error>> {
+        self.write_left_title(INFO, pos_y);
+        let rows = self.info_rows();
+        self.write_left_rows(&rows, pos_y, self.theme.font_sizes.small)
+    }
+
+    fn write_social(&mut self, pos_y: Pt) -> Result<(), Box<dyn Error>> {
+        self.write_left_title(SOCIALS, pos_y);
+        let rows = self.social_rows();
+        self.write_left_rows(&rows, pos_y, self.theme.font_sizes.text)
+    }
+
+    fn write_languages(&self, pos_y: Pt) {
+        self.write_left_title(LANGUAGES, pos_y);
+        let sizes = &self.theme.font_sizes;
+        let line_heights = &self.theme.line_heights;
+        let baseline = pos_y - Pt(sizes.heading) - Pt(line_heights.heading) - Pt(line_heights.list);
+
+        self.layer.begin_text_section();
+        self.layer.set_fill_color(self.secondary_color.clone());
+        self.layer.set_text_cursor(self.padding(), baseline.into());
+        self.layer.set_line_height(line_heights.list);
+        let width = self
+            .resume
+            .languages
+            .iter()
+            .map(|l| l.language.len())
+            .max()
+            .unwrap_or(0)
+            + 4;
+
+        self.resume
+            .languages
+            .iter()
+            .for_each(|Language { language, fluency }| {
+                self.layer.set_font(&self.font_bold, sizes.text);
+                self.layer.write_text(
+                    format!("- {:<width$}", language, width = width),
+                    &self.font_bold,
+                );
+                if let Some(fluency) = fluency {
+                    self.layer.set_font(&self.font_thin, sizes.text);
+                    self.layer.write_text(fluency, &self.font_thin);
+                }
+                self.layer.add_line_break();
+            });
+        self.layer.end_text_section();
+    }
+
+    /// Writes the skills from `pos_y`, continuing on the next page those
+    /// running into `bottom`, and returns the position right after them.
+    fn write_skills(&mut self, pos_y: Pt, bottom: &mut Pt) -> Pt {
+        self.write_left_title(SKILLS, pos_y);
+
+        let offset_x = self.padding();
+        let line_height = Pt(self.theme.line_heights.skills);
+        let width = self.left_text_width();
+        let colors = (self.secondary_color.clone(), self.primary_color.clone());
+        let skills: Vec<Skill> = self.resume.skills.to_vec();
+        let mut pos_y = pos_y - self.left_title_height();
+        for skill in skills {
+            let height = self.skill_height(&skill);
+            if pos_y - height < *bottom && pos_y < self.content_top() {
+                self.continue_left_column();
+                pos_y = self.content_top();
+                *bottom = self.page_margin();
+            }
+            let Skill {
+                name,
+                level,
+                keywords,
+                ..
+            } = skill;
+            let baseline = pos_y - line_height;
+            self.layer.begin_text_section();
+            self.layer.set_fill_color(self.secondary_color.clone());
+            self.layer.set_text_cursor(offset_x, baseline.into());
+            self.layer
+                .set_font(&self.font_bold, self.theme.font_sizes.text);
+            self.layer
+                .write_text(format!("- {}", name), &self.font_bold);
+            if let Some(level) = level {
+                self.layer
+                    .set_font(&self.font_thin, self.theme.font_sizes.text);
+                self.layer
+                    .write_text(format!("  ({})", level), &self.font_thin);
+            }
+            self.layer.end_text_section();
+
+            if !keywords.is_empty() {
+                let links = self.write_highlights(
+                    &keywords,
+                    (offset_x.into(), baseline - line_height),
+                    width,
+                    &self.font_thin,
+                    self.theme.font_sizes.text,
+                    &colors,
+                );
+                self.links.extend(links);
+            }
+            pos_y -= height;
+        }
+        pos_y
+    }
+
+    /// Height of a skill of the left column, keywords included.
+    fn skill_height(&self, skill: &Skill) -> Pt {
+        Pt(self.theme.line_heights.skills)
+            + self.highlights_height(
+                &skill.keywords,
+                self.left_text_width(),
+                &self.font_thin,
+                self.theme.font_sizes.text,
+            )
+    }
+
+    fn info_rows(&self) -> Vec<Row> {
+        let basics = &self.resume.basics;
+        let mut rows = vec![];
+        if let Some(email) = &basics.email {
+            let uri = format!("mailto:{}", email);
+            rows.push(("envelope".to_string(), email.clone(), Some(uri)));
+        }
+        if let Some(phone) = &basics.phone {
+            rows.push(("phone".to_string(), phone.clone(), Some(phone_uri(phone))));
+        }
+        if let Some(Location {
+            city: Some(city),
+            country_code,
+            ..
+        }) = &basics.location
+        {
+            let text = match country_code {
+                None => city.clone(),
+                Some(country_code) => format!("{}, {}", city, country_code),
+            };
+            rows.push(("map-marker".to_string(), text, None));
+        }
+        if let Some(birthday) = &basics.birthday {
+            let text = format!("{} ({}yo)", birthday.format("%d %b %Y"), birthday.age());
+            rows.push(("cake".to_string(), text, None));
+        }
+        rows
+    }
+
+    fn social_rows(&self) -> Vec<Row> {
+        self.resume
+            .basics
+            .profiles
+            .iter()
+            .map(|profile| {
+                let uri = Some(profile.url.clone()).filter(|url| !url.is_empty());
+                (
+                    profile.network.to_lowercase(),
+                    profile.username.clone(),
+                    uri,
+                )
+            })
+            .collect()
+    }
+
+    /// Height of a titled section made of `rows`, nil without rows.
+    fn left_rows_height(&self, rows: &[Row], font_size: f64) -> Pt {
+        if rows.is_empty() {
+            return Pt(0.);
+        }
+        let line_heights = &self.theme.line_heights;
+        let width = self.left_text_width() - ICON_INDENT;
+        rows.iter().fold(
+            Pt(self.theme.font_sizes.heading) + Pt(line_heights.heading),
+            |height, (_, text, _)| {
+                let lines = wrapped_lines(text, width, &self.font_regular, font_size);
+                height + Pt(line_heights.list) + Pt(line_heights.text) * (lines - 1) as f64
+            },
+        )
+    }
+
+    /// Writes `rows` below the title of a section starting at `pos_y`, their
+    /// text wrapping next to their icon.
+    fn write_left_rows(
+        &mut self,
+        rows: &[Row],
+        pos_y: Pt,
+        font_size: f64,
+    ) -> Result<(), Box<dyn Error>> {
+        let line_heights = self.theme.line_heights.clone();
+        let width = self.left_text_width() - ICON_INDENT;
+        let mut baseline = pos_y - Pt(self.theme.font_sizes.heading) - Pt(line_heights.heading);
+        for (icon, text, uri) in rows {
+            baseline -= Pt(line_heights.list);
+            if let Some(uri) = uri {
+                self.link_left_row(uri.clone(), baseline);
+            }
+            self.layer.begin_text_section();
+            self.layer.set_text_cursor(self.padding(), baseline.into());
+            self.write_social_icon(icon, self.theme.font_sizes.icon)?;
+            self.layer.end_text_section();
+
+            self.layer.set_fill_color(self.secondary_color.clone());
+            self.layer.begin_text_section();
+            self.layer
+                .set_text_cursor(self.padding() + ICON_INDENT, baseline.into());
+            self.layer.set_line_height(line_heights.text);
+            self.layer.set_font(&self.font_regular, font_size);
+            self.write_bounded(text, width, &self.font_regular, font_size);
+            self.layer.end_text_section();
+
+            let lines = wrapped_lines(text, width, &self.font_regular, font_size);
+            baseline -= Pt(line_heights.text) * (lines - 1) as f64;
+        }
+        Ok(())
+    }
+
+    /// Height of the title of a section.
+    fn left_title_height(&self) -> Pt {
+        Pt(self.theme.font_sizes.heading) + Pt(self.theme.line_heights.heading)
+    }
+
+    /// Writes the underlined title of the section starting at `pos_y`.
+    pub(super) fn write_left_title(&self, title: &str, pos_y: Pt) {
+        let font_size = self.theme.font_sizes.heading;
+        self.layer.set_fill_color(self.secondary_color.clone());
+        self.layer.begin_text_section();
+        self.write_underlined_text(
+            title,
+            font_size,
+            self.padding(),
+            (pos_y - Pt(font_size)).into(),
+            self.left_text_width(),
+        );
+        self.layer.end_text_section();
+    }
+
+    /// Makes the row of the left column at `baseline` clickable.
+    fn link_left_row(&mut self, uri: String, baseline: Pt) {
+        let font_size = self.theme.font_sizes.icon;
+        self.links.push(Link {
+            page: self.page,
+            lower_left: (self.padding().into(), baseline - Pt(font_size / 4.)),
+            upper_right: (
+                (self.left_column_width() - self.padding()).into(),
+                baseline + Pt(font_size),
+            ),
+            uri,
+        });
+    }
+
+    /// Width of the text of the left column, inside its padding.
+    pub(super) fn left_text_width(&self) -> Mm {
+        self.left_column_width() - self.padding() - self.padding()
+    }
+}
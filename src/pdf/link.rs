@@ -0,0 +1,47 @@
+use printpdf::lopdf::{dictionary, Document, Object};
+use printpdf::Pt;
+
+/// Clickable area of a page, opening `uri`.
+#[derive(Debug)]
+pub struct Link {
+    pub page: usize,
+    pub lower_left: (Pt, Pt),
+    pub upper_right: (Pt, Pt),
+    pub uri: String,
+}
+
+impl Link {
+    fn annotation(&self) -> Object {
+        let (x1, y1) = self.lower_left;
+        let (x2, y2) = self.upper_right;
+        Object::from(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Link",
+            "Rect" => vec![x1.0.into(), y1.0.into(), x2.0.into(), y2.0.into()],
+            "Border" => vec![0.into(), 0.into(), 0.into()],
+            "A" => dictionary! {
+                "S" => "URI",
+                "URI" => Object::string_literal(self.uri.as_str()),
+            },
+        })
+    }
+}
+
+/// Adds the link annotations to the pages of `document`, printpdf having no
+/// support for them.
+pub fn annotate(document: &mut Document, links: &[Link]) -> Result<(), printpdf::lopdf::Error> {
+    for (number, page_id) in document.get_pages() {
+        let annotations: Vec<Object> = links
+            .iter()
+            .filter(|link| link.page + 1 == number as usize)
+            .map(|link| Object::Reference(document.add_object(link.annotation())))
+            .collect();
+        if !annotations.is_empty() {
+            document
+                .get_object_mut(page_id)?
+                .as_dict_mut()?
+                .set("Annots", annotations);
+        }
+    }
+    Ok(())
+}
@@ -1,345 +1,397 @@
+mod about;
+mod ats;
+mod column;
+mod font;
 mod font_awesome;
+mod highlights;
 mod image;
+pub(crate) mod inline;
+mod left;
+mod link;
+mod paper;
+mod sections;
 mod shape;
+mod template;
 mod text;
-mod timeline;
 
-use crate::resume::{Language, Location, Resume, Skill};
-use chrono::prelude::*;
+use crate::resume::{phone_uri, Language, Location, Resume, Skill};
+use crate::theme::{self, Theme};
+use crate::timeline::*;
+use crate::titles::*;
+use font::Font;
 use font_awesome::FontAwesome;
+use link::Link;
 use printpdf::utils::calculate_points_for_circle;
 use printpdf::*;
 use std::convert::From;
 use std::error::Error;
 use std::fs::File;
-use std::io::{BufWriter, Cursor};
+use std::io::BufWriter;
 use std::path::PathBuf;
-use timeline::*;
+use template::Region;
+use text::wrapped_lines;
+
+pub use about::AboutPlacement;
+pub use paper::Paper;
+pub use template::{Layout, Section, Template};
 
 const FONT_REGULAR: &[u8] = include_bytes!("../fonts/liberation.ttf");
 const FONT_BOLD: &[u8] = include_bytes!("../fonts/liberation-bold.ttf");
 const FONT_THIN: &[u8] = include_bytes!("../fonts/liberation-thin.ttf");
 
-const DOC_WIDTH: Mm = Mm(210.0);
-const DOC_HEIGHT: Mm = Mm(297.0);
-
-const PROFILE_SIZE: Mm = Mm(74.5);
-const PROFILE_X_OFFSET: Mm = Mm(5.);
-const PROFILE_Y_OFFSET: Mm = Mm(PROFILE_SIZE.0);
-
 const RADIUS: Pt = Pt(5.);
 
-const LEFT_COLUMN_SIZE: Mm = PROFILE_SIZE;
-pub(super) const RIGHT_COLUMN_HEIGHT: Mm = Mm(74.5);
+const EVENT_TEXT_OFFSET: Pt = Pt(20.);
+const EVENT_SUMMARY_OFFSET: Mm = Mm(7.);
+const EVENT_GUTTER: Mm = Mm(3.);
 
 const DPI: f64 = 300.0;
 
-const INFO: &str = "Info";
-const LANGUAGES: &str = "Spoken languages";
-const SOCIALS: &str = "Social";
-const SKILLS: &str = "Skills";
-
-const DATE_FORMAT: &str = "%b %Y";
+/// Vertical position of an event, along with its group when the event starts
+/// it, or starts a page, and therefore shows its header.
+type Placement<'a> = (Pt, &'a Event, Option<&'a Group>);
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub omit_left_column: bool,
+    pub ats: bool,
+    pub volunteer_section: bool,
+    pub normalize_institutions: bool,
+    pub paper: Paper,
+    pub about: AboutPlacement,
+    pub template: Template,
+    pub layout: Layout,
+}
 
 pub struct Pdf {
     resume: Resume,
+    theme: Theme,
+    options: Options,
     font_awesome: FontAwesome,
-    font_regular: IndirectFontRef,
-    font_bold: IndirectFontRef,
-    font_thin: IndirectFontRef,
+    font_regular: Font,
+    font_bold: Font,
+    font_thin: Font,
+    font_italic: Font,
     layer: PdfLayerReference,
+    /// Layer of each page added so far
+    layers: Vec<PdfLayerReference>,
+    page: usize,
+    links: Vec<Link>,
     doc: PdfDocumentReference,
     primary_color: Color,
     secondary_color: Color,
 }
 
 impl Pdf {
-    pub fn new(resume: Resume) -> Result<Self, Box<dyn Error>> {
-        let (doc, page1, layer1) = PdfDocument::new("Resume", DOC_WIDTH, DOC_HEIGHT, "Layer 1");
-        let font_regular = doc.add_external_font(Cursor::new(FONT_REGULAR))?;
-        let font_bold = doc.add_external_font(Cursor::new(FONT_BOLD))?;
-        let font_thin = doc.add_external_font(Cursor::new(FONT_THIN))?;
+    pub fn new(resume: Resume, theme: Theme, options: Options) -> Result<Self, Box<dyn Error>> {
+        options.layout.check(options.template, options.about)?;
+        let theme = options.template.theme(theme);
+        let Paper { width, height } = options.paper;
+        let (doc, page1, layer1) = PdfDocument::new("Resume", width, height, "Layer 1");
+        let fonts = &theme.fonts;
+        let font_regular = font::load(&doc, fonts.regular.as_deref(), FONT_REGULAR)?;
+        let font_bold = font::load(&doc, fonts.bold.as_deref(), FONT_BOLD)?;
+        let font_thin = font::load(&doc, fonts.light.as_deref(), FONT_THIN)?;
+        // The italic font falls back to the light one, embedded only once
+        let font_italic = match fonts.italic {
+            Some(_) => font::load(&doc, fonts.italic.as_deref(), FONT_THIN)?,
+            None => font_thin.clone(),
+        };
 
-        let font_awesome = FontAwesome::new(font_regular.clone());
+        // The icons only exist in the embedded regular font
+        let font_icons = match fonts.regular {
+            None => (*font_regular).clone(),
+            Some(_) => (*font::load(&doc, None, FONT_REGULAR)?).clone(),
+        };
+        let font_awesome = FontAwesome::new(font_icons);
 
         let layer = doc.get_page(page1).get_layer(layer1);
 
+        let primary_color = theme.colors.primary.into();
+        let secondary_color = theme.colors.secondary.into();
+
         Ok(Self {
             resume,
+            theme,
+            options,
             font_awesome,
             font_regular,
             font_bold,
             font_thin,
+            font_italic,
+            layers: vec![layer.clone()],
             layer,
+            page: 0,
+            links: vec![],
             doc,
-            primary_color: Color::Cmyk(Cmyk::new(0.78, 0.62, 0.66, 0.71, None)),
-            secondary_color: Color::Rgb(Rgb::new(1., 1., 1., None)),
+            primary_color,
+            secondary_color,
         })
     }
 
     pub fn save(self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
         debug!("Generating pdf: {:?}", filename);
-        self.draw_left_background();
-        let start = self.add_profile_picture();
-
-        self.social_qr_code()?;
-        self.write_bio(start);
-
-        self.write_info(start)?;
-        self.write_social(start);
-        self.write_languages(start);
-        self.write_skills(start);
-
-        self.write_timeline();
-        self.doc
-            .save(&mut BufWriter::new(File::create(filename)?))?;
+        let mut document = self.render()?;
+        document.save_to(&mut BufWriter::new(File::create(filename)?))?;
         Ok(())
     }
 
-    fn write_bio(&self, start: Mm) {
-        self.layer
-            .set_fill_color(Color::Rgb(Rgb::new(1., 1., 1., None)));
-        let offset_x = PROFILE_X_OFFSET;
-        let offset_y = DOC_HEIGHT - (start + Mm(10.));
-
-        self.layer.begin_text_section();
-        self.layer.set_font(&self.font_bold, 20.0);
-        self.layer.set_text_cursor(offset_x, offset_y);
-        self.layer.set_line_height(18.0);
-        self.resume
-            .basics
-            .name
-            .to_uppercase()
-            .split_whitespace()
-            .for_each(|part| {
-                self.layer.write_text(part, &self.font_bold);
-                self.layer.add_line_break();
-            });
+    /// Lays out the resume and returns the resulting document, links included.
+    pub fn render(mut self) -> Result<lopdf::Document, Box<dyn Error>> {
+        if self.options.ats {
+            self.write_ats();
+        } else {
+            self.write_regions()?;
+        }
 
-        self.layer.set_font(&self.font_regular, 10.0);
-        self.layer
-            .write_text(&self.resume.basics.label, &self.font_regular);
-        self.layer.end_text_section();
+        let mut document = lopdf::Document::load_mem(&self.doc.save_to_bytes()?)?;
+        link::annotate(&mut document, &self.links)?;
+        Ok(document)
     }
 
-    fn write_info(&self, start: Mm) -> Result<(), Box<dyn Error>> {
-        self.layer.set_fill_color(self.secondary_color.clone());
-        let offset_x = PROFILE_X_OFFSET;
-        let offset_y = DOC_HEIGHT - (start + Mm(35.));
-
-        self.layer.begin_text_section();
-        self.layer.set_line_height(18.0);
-        self.write_underlined_text(INFO, 12, offset_x, offset_y);
-        self.layer.add_line_break();
-        self.layer.set_line_height(16.0);
-
-        self.layer.add_line_break();
-
-        self.font_awesome.print_icon(
-            self.layer.clone(),
-            "envelope",
-            12,
-            self.secondary_color.clone(),
-        )?;
-
-        self.layer.set_font(&self.font_regular, 9.0);
-        self.layer.write_text(
-            format!("   {}", &self.resume.basics.email),
-            &self.font_regular,
-        );
-
-        if let Some(phone) = &self.resume.basics.phone {
-            self.layer.add_line_break();
-            self.font_awesome.print_icon(
-                self.layer.clone(),
-                "phone",
-                12,
-                self.secondary_color.clone(),
-            )?;
-            self.layer.set_font(&self.font_regular, 9.0);
-            self.layer
-                .write_text(format!("   {}", phone), &self.font_regular);
-        }
-        if let Some(Location {
-            city: Some(city),
-            country_code,
-            ..
-        }) = &self.resume.basics.location
-        {
-            self.layer.add_line_break();
-            self.font_awesome.print_icon(
-                self.layer.clone(),
-                "map-marker",
-                12,
-                self.secondary_color.clone(),
-            )?;
-            self.layer.set_font(&self.font_regular, 9.0);
-            let text = match country_code.as_ref() {
-                None => String::new(),
-                Some(country_code) => format!(", {}", country_code),
-            };
-            self.layer
-                .write_text(format!("   {}{}", city, text), &self.font_regular);
+    /// Writes the sections of the template in their regions, the left
+    /// column first.
+    fn write_regions(&mut self) -> Result<(), Box<dyn Error>> {
+        let template = self.options.template;
+        let about = self.options.about;
+        let layout = self.options.layout.clone();
+        if template.has_left_column() {
+            self.draw_left_background();
+            let start = self.add_profile_picture();
+            let bottom = self.social_qr_code()? + self.page_margin();
+            let pos_y = Pt::from(self.page_height() - start - self.padding());
+            let sections = layout.sections(template, Region::Left, about);
+            self.write_left_sections(&sections, pos_y, bottom)?;
+            self.turn_to(0);
         }
 
-        if let Some(birthday) = &self.resume.basics.birthday {
-            self.layer.add_line_break();
-            self.font_awesome.print_icon(
-                self.layer.clone(),
-                "cake",
-                12,
-                self.secondary_color.clone(),
-            )?;
-            let age = Local::now()
-                .naive_utc()
-                .date()
-                .signed_duration_since(*birthday)
-                .num_weeks()
-                / 52;
-            self.layer.set_font(&self.font_regular, 9.0);
-            self.layer.write_text(
-                format!("    {} ({}yo)", birthday.format("%d %b %Y"), age),
-                &self.font_regular,
-            );
+        let mut pos_y = self.content_top();
+        for section in layout.sections(template, Region::Main, about) {
+            pos_y = self.write_main_section(section, pos_y)?;
         }
-        self.layer.end_text_section();
         Ok(())
     }
 
-    fn write_social(&self, start: Mm) {
-        self.layer.begin_text_section();
-        self.layer.set_fill_color(self.secondary_color.clone());
-        let offset_x = PROFILE_X_OFFSET;
-        let offset_y = DOC_HEIGHT - (start + Mm(25.) + Mm(48.));
-        self.layer.set_line_height(18.0);
-        self.write_underlined_text(SOCIALS, 12, offset_x, offset_y);
-        self.layer.add_line_break();
-        self.layer.set_line_height(16.0);
-
-        self.resume.basics.profiles.iter().for_each(|profile| {
-            self.layer.add_line_break();
-            let network = profile.network.to_lowercase();
-            self.write_social_icon(&network, 12).unwrap();
-            self.layer.set_fill_color(self.secondary_color.clone());
-            self.layer.set_font(&self.font_regular, 10.0);
-            self.layer
-                .write_text(format!("   {}", &profile.username), &self.font_regular);
-        });
+    /// Writes `section` in the main column from `pos_y` and returns the
+    /// position right after it.
+    fn write_main_section(&mut self, section: Section, pos_y: Pt) -> Result<Pt, Box<dyn Error>> {
+        Ok(match section {
+            Section::Bio => self.write_header(pos_y),
+            Section::About => self.write_about(pos_y),
+            Section::Info => self.write_contact_line(pos_y),
+            Section::Social => self.write_profile_line(pos_y),
+            Section::Languages => self.write_language_list(pos_y),
+            Section::Skills => self.write_skill_list(pos_y),
+            Section::Timeline => self.write_timeline(pos_y),
+            Section::Projects => self.write_projects(pos_y),
+            Section::Awards => self.write_awards(pos_y),
+            Section::Certificates => self.write_certificates(pos_y),
+            Section::Publications => self.write_publications(pos_y),
+            Section::Interests => self.write_interests(pos_y),
+            Section::References => self.write_references(pos_y),
+        })
+    }
 
-        self.layer.end_text_section();
+    /// Moves to the next page, adding it unless the left column already
+    /// continued there.
+    fn add_page(&mut self) {
+        if self.page + 1 < self.layers.len() {
+            self.turn_to(self.page + 1);
+            return;
+        }
+        let Paper { width, height } = self.options.paper;
+        let (page, layer) = self.doc.add_page(width, height, "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.layers.push(self.layer.clone());
+        self.page += 1;
+        if !self.options.omit_left_column
+            && !self.options.ats
+            && self.options.template.has_left_column()
+        {
+            self.draw_left_background();
+        }
     }
 
-    fn write_languages(&self, start: Mm) {
-        self.layer.begin_text_section();
-        self.layer.set_fill_color(self.secondary_color.clone());
-        let offset_x = PROFILE_X_OFFSET;
-        let offset_y = DOC_HEIGHT - (start + Mm(25.) + Mm(85.));
-        self.layer.set_line_height(18.0);
+    /// Goes back to the `page`-th page, which has already been added.
+    fn turn_to(&mut self, page: usize) {
+        self.layer = self.layers[page].clone();
+        self.page = page;
+    }
 
-        self.write_underlined_text(LANGUAGES, 12, offset_x, offset_y);
+    fn write_timeline(&mut self, pos_y: Pt) -> Pt {
+        let (timeline, volunteering) =
+            Timeline::from_resume(&self.resume, self.options.volunteer_section);
 
-        self.layer.add_line_break();
+        let normalize = self.options.normalize_institutions;
+        let pos_y = self.write_events(&timeline.groups(normalize), pos_y);
 
-        self.layer.set_line_height(16.0);
-        let width = self
-            .resume
-            .languages
-            .iter()
-            .map(|l| l.language.len())
-            .max()
-            .unwrap_or(0)
-            + 4;
+        let groups = volunteering.groups(normalize);
+        if groups.is_empty() {
+            pos_y
+        } else {
+            let pos_y = self.write_section_title(VOLUNTEERING, pos_y);
+            self.write_events(&groups, pos_y)
+        }
+    }
 
-        self.resume
-            .languages
-            .iter()
-            .for_each(|Language { language, fluency }| {
-                self.layer.add_line_break();
-                self.layer.set_font(&self.font_bold, 10.0);
-                self.layer.write_text(
-                    format!("- {:<width$}", language, width = width),
-                    &self.font_bold,
-                );
-                if let Some(fluency) = fluency {
-                    self.layer.set_font(&self.font_thin, 10.0);
-                    self.layer.write_text(fluency, &self.font_thin);
-                }
-            });
-        self.layer.end_text_section();
+    /// Makes sure `height` fits below `pos_y`, moving to a new page
+    /// otherwise, and returns where to start writing.
+    fn reserve(&mut self, pos_y: Pt, height: Pt) -> Pt {
+        if pos_y - height < self.page_margin() && pos_y < self.content_top() {
+            self.add_page();
+            self.content_top()
+        } else {
+            pos_y
+        }
     }
 
-    fn write_skills(&self, start: Mm) {
+    fn write_section_title(&mut self, title: &str, pos_y: Pt) -> Pt {
+        let section_height = Pt(self.theme.spacing.section);
+        let needed = section_height + EVENT_TEXT_OFFSET + Pt(self.theme.line_heights.text);
+        let pos_y = self.reserve(pos_y, needed);
+        let font_size = self.theme.font_sizes.heading;
+        self.layer.set_fill_color(self.primary_color.clone());
         self.layer.begin_text_section();
-        self.layer.set_fill_color(self.secondary_color.clone());
-        let offset_x = PROFILE_X_OFFSET;
-        let offset_y = DOC_HEIGHT - (start + Mm(25.) + Mm(85.) + Mm(40.));
-        self.layer.set_line_height(18.0);
-
-        self.write_underlined_text(SKILLS, 12, offset_x, offset_y);
-
-        self.layer.add_line_break();
-        self.layer.set_line_height(14.0);
-        self.layer.add_line_break();
-        self.resume
-            .skills
-            .iter()
-            .for_each(|Skill { name, keywords, .. }| {
-                self.layer.set_font(&self.font_bold, 10.0);
-                self.layer
-                    .write_text(format!("- {}", name), &self.font_bold);
-                self.layer.add_line_break();
-                self.layer.set_font(&self.font_thin, 10.0);
-                self.write_bounded(&keywords.join("  -  "), 36);
-            });
+        self.write_underlined_text(
+            title,
+            font_size,
+            self.right_column_offset(),
+            (pos_y - Pt(font_size)).into(),
+            self.right_column_width(),
+        );
         self.layer.end_text_section();
+        pos_y - section_height
     }
 
-    fn write_timeline(&self) {
-        let mut timeline = Timeline::new();
+    /// Draws the grouped events as a timeline starting at `pos_y`, continuing
+    /// on new pages as needed, and returns the position right after the last
+    /// one.
+    fn write_events(&mut self, groups: &[Group], pos_y: Pt) -> Pt {
+        if groups.is_empty() {
+            return pos_y;
+        }
+        let (pages, end_y) = self.layout_timeline(groups, pos_y);
+        let offset_x: Pt = self.timeline_offset().into();
+        let height: Pt = self.page_height().into();
+        let last_page = pages.len().saturating_sub(1);
+        // The first event may not fit on the current page, leaving it empty
+        let first_page = pages
+            .iter()
+            .position(|placements| !placements.is_empty())
+            .unwrap_or(0);
 
-        self.resume.work.iter().cloned().for_each(|work| {
-            let event = Event::from(work);
-            timeline.add(event);
+        pages.iter().enumerate().for_each(|(page, placements)| {
+            if page > 0 {
+                self.add_page();
+            }
+            if placements.is_empty() {
+                return;
+            }
+            let line_top = match placements.first() {
+                Some((pos_y, _, _)) if page == first_page => *pos_y,
+                _ => height,
+            };
+            let line_bottom = match placements.last() {
+                Some((pos_y, _, _)) if page == last_page => *pos_y,
+                _ => Pt(0.),
+            };
+            let line = Line {
+                points: shape::rectangle_points(
+                    offset_x,
+                    line_bottom,
+                    Pt(2.),
+                    line_top - line_bottom,
+                ),
+                is_closed: true,
+                has_fill: true,
+                has_stroke: false,
+                is_clipping_path: false,
+            };
+            self.layer.set_fill_color(self.primary_color.clone());
+            self.layer.add_shape(line);
+
+            placements.iter().for_each(|(pos_y, event, header)| {
+                self.write_event(event, *header, offset_x, *pos_y)
+            });
         });
+        end_y
+    }
 
-        self.resume.education.iter().cloned().for_each(|education| {
-            let event = Event::from(education);
-            timeline.add(event);
+    /// Distributes the events over as many pages as needed, returning for
+    /// each page the vertical position of its events, the first page being
+    /// the current one.
+    fn layout_timeline<'a>(
+        &self,
+        groups: &'a [Group],
+        mut pos_y: Pt,
+    ) -> (Vec<Vec<Placement<'a>>>, Pt) {
+        let top_y = self.content_top();
+        let page_margin = self.page_margin();
+        let mut pages = vec![vec![]];
+
+        groups.iter().for_each(|group| {
+            group.events.iter().enumerate().for_each(|(index, event)| {
+                let mut header = if index == 0 { Some(group) } else { None };
+                let mut event_height = self.event_height(event, header);
+
+                if pos_y - event_height < page_margin && pos_y < top_y {
+                    pages.push(vec![]);
+                    pos_y = top_y;
+                    header = Some(group);
+                    event_height = self.event_height(event, header);
+                }
+                pages.last_mut().unwrap().push((pos_y, event, header));
+                pos_y -= event_height + Pt(self.theme.spacing.event);
+            });
         });
+        (pages, pos_y)
+    }
 
-        let events = timeline.events();
-        let offset_x: Pt = (LEFT_COLUMN_SIZE + Mm((DOC_WIDTH.0 - LEFT_COLUMN_SIZE.0) / 2.)).into();
-        let height: Pt = DOC_HEIGHT.into();
-        let top_y: Pt = height - Pt(20.);
+    fn event_height(&self, event: &Event, header: Option<&Group>) -> Pt {
+        let sizes = &self.theme.font_sizes;
+        let left_width = self.event_label_width();
+        let right_width = self.event_summary_width();
 
-        let event_height: Pt = Pt(top_y.0 / events.len() as f64);
+        let mut left_lines =
+            wrapped_lines(&event.label, left_width, &self.font_regular, sizes.small) + 1;
+        if let Some(group) = header {
+            let (institution, location) = event.institution_and_location();
+            left_lines += wrapped_lines(institution, left_width, &self.font_bold, sizes.title);
+            if !location.is_empty() {
+                left_lines +=
+                    wrapped_lines(location.trim(), left_width, &self.font_regular, sizes.small);
+            }
+            if group.events.len() > 1 {
+                left_lines += 1;
+            }
+        }
 
-        let line_height = Pt(((events.len() - 1) as f64) * event_height.0);
+        let line_height = Pt(self.theme.line_heights.text);
+        let summary_lines = event
+            .summary
+            .as_ref()
+            .map(|summary| {
+                self.markdown_lines(summary, right_width, &self.font_regular, sizes.small)
+            })
+            .unwrap_or(0);
+        let mut right_height = line_height * summary_lines as f64;
+        if !event.highlights.is_empty() {
+            right_height += line_height
+                + self.highlights_height(
+                    &event.highlights,
+                    right_width,
+                    &self.font_regular,
+                    sizes.small,
+                );
+        }
 
-        let line = Line {
-            points: shape::rectangle_points(offset_x, top_y - line_height, Pt(2.), line_height),
-            is_closed: true,
-            has_fill: true,
-            has_stroke: false,
-            is_clipping_path: false,
-        };
-        self.layer.set_fill_color(self.primary_color.clone());
-        self.layer.add_shape(line);
-        let mut previous: Option<&str> = None;
-        events.iter().enumerate().for_each(|(i, event)| {
-            let indexed_event = (i, event);
-            let is_same_has_previous_event =
-                previous.map(|p| p == event.institution).unwrap_or(false);
-
-            self.write_event(
-                indexed_event,
-                is_same_has_previous_event,
-                offset_x,
-                top_y,
-                event_height,
-            );
-            previous = Some(&event.institution)
-        });
+        let left_height = line_height * left_lines as f64;
+        EVENT_TEXT_OFFSET
+            + if left_height > right_height {
+                left_height
+            } else {
+                right_height
+            }
     }
 
     fn add_profile_picture(&self) -> Mm {
@@ -353,7 +405,8 @@ impl Pdf {
                     Mm(15.)
                 }
                 Ok(image) => {
-                    let pt_size: Pt = PROFILE_SIZE.into();
+                    let profile_size = self.left_column_width();
+                    let pt_size: Pt = profile_size.into();
 
                     let scale_x = pt_size.0 / image.image.width.into_pt(DPI).0;
                     let scale_y = pt_size.0 / image.image.height.into_pt(DPI).0;
@@ -362,30 +415,20 @@ impl Pdf {
                         self.layer.clone(),
                         ImageTransform {
                             translate_x: Some(Mm(0.)),
-                            translate_y: Some(DOC_HEIGHT - PROFILE_Y_OFFSET),
+                            translate_y: Some(self.page_height() - profile_size),
                             rotate: None,
                             scale_x: Some(scale_x),
                             scale_y: Some(scale_y),
                             dpi: Some(DPI),
                         },
                     );
-                    RIGHT_COLUMN_HEIGHT
+                    profile_size
                 }
             })
             .unwrap_or(Mm(15.))
     }
 
-    fn write_event(
-        &self,
-        indexed_event: (usize, &Event),
-        is_same_has_previous_event: bool,
-        offset_x: Pt,
-        offset_y: Pt,
-        height: Pt,
-    ) {
-        let (i, event) = indexed_event;
-        let pos_y = Pt(offset_y.0 - (i as f64 * height.0));
-
+    fn write_event(&mut self, event: &Event, header: Option<&Group>, offset_x: Pt, pos_y: Pt) {
         let outer_circle = Line {
             points: calculate_points_for_circle(RADIUS, offset_x + Pt(1.), pos_y),
             is_closed: true,
@@ -395,8 +438,17 @@ impl Pdf {
         };
 
         self.layer.set_outline_color(self.primary_color.clone());
-        if is_same_has_previous_event {
+        if header.is_none() {
+            // Later roles of a group get a dot of their own
+            let dot = Line {
+                points: calculate_points_for_circle(Pt(2.), offset_x + Pt(1.), pos_y),
+                is_closed: true,
+                has_fill: true,
+                has_stroke: false,
+                is_clipping_path: false,
+            };
             self.layer.set_fill_color(self.primary_color.clone());
+            self.layer.add_shape(dot);
         } else {
             match event.event_type {
                 EventType::Work => {
@@ -417,38 +469,70 @@ impl Pdf {
                     self.layer.set_fill_color(self.primary_color.clone());
                     self.layer.add_shape(outer_circle);
                 }
+                EventType::Volunteer => {
+                    let diamond = Line {
+                        points: shape::diamond_points(offset_x + Pt(1.), pos_y, RADIUS + Pt(1.)),
+                        is_closed: true,
+                        has_fill: true,
+                        has_stroke: true,
+                        is_clipping_path: false,
+                    };
+                    self.layer.set_fill_color(self.secondary_color.clone());
+                    self.layer.add_shape(diamond);
+                }
             }
         }
+        self.layer.set_fill_color(self.primary_color.clone());
         self.layer.begin_text_section();
 
-        self.layer.set_font(&self.font_bold, 12.0);
-        self.layer.set_line_height(12.0);
+        let sizes = self.theme.font_sizes.clone();
+        self.layer.set_font(&self.font_bold, sizes.title);
+        self.layer.set_line_height(self.theme.line_heights.text);
 
-        self.layer
-            .set_text_cursor(LEFT_COLUMN_SIZE + Mm(5.), (pos_y - Pt(20.)).into());
-
-        let end_date = event
-            .end_date
-            .map(|end_date| end_date.format(DATE_FORMAT).to_string())
-            .unwrap_or_else(|| "Today".to_string());
-
-        if !is_same_has_previous_event {
-            let mut split_iter = event.institution.split(',');
-            let institution = split_iter.next().unwrap();
-            let location = split_iter.collect::<Vec<_>>().join(",");
-            self.write_bounded(institution, 30);
-            self.layer.set_font(&self.font_regular, 9.0);
+        self.layer.set_text_cursor(
+            self.right_column_offset(),
+            (pos_y - EVENT_TEXT_OFFSET).into(),
+        );
+
+        let left_width = self.event_label_width();
+        if let Some(group) = header {
+            let (institution, location) = event.institution_and_location();
+            if let Some(website) = &event.website {
+                let lines = wrapped_lines(institution, left_width, &self.font_bold, sizes.title);
+                let width = match lines {
+                    1 => self.font_bold.text_width(institution, sizes.title),
+                    _ => left_width.into(),
+                };
+                self.link_text(
+                    website.clone(),
+                    (self.right_column_offset().into(), pos_y - EVENT_TEXT_OFFSET),
+                    width,
+                    lines,
+                    sizes.title,
+                );
+            }
+            self.write_bounded(institution, left_width, &self.font_bold, sizes.title);
+            self.layer.set_font(&self.font_regular, sizes.small);
             if !location.is_empty() {
-                self.write_bounded(location.trim(), 30);
+                self.write_bounded(location.trim(), left_width, &self.font_regular, sizes.small);
+            }
+            if group.events.len() > 1 {
+                let (start_date, end_date) = group.period();
+                self.layer.set_font(&self.font_thin, sizes.small);
+                self.layer.write_text(
+                    format_period(&start_date, end_date.as_ref()),
+                    &self.font_thin,
+                );
+                self.layer.add_line_break();
             }
         };
 
-        self.layer.set_font(&self.font_regular, 9.0);
-        self.write_bounded(&event.label, 30);
-        self.layer.set_font(&self.font_thin, 9.0);
+        self.layer.set_font(&self.font_regular, sizes.small);
+        self.write_bounded(&event.label, left_width, &self.font_regular, sizes.small);
+        self.layer.set_font(&self.font_thin, sizes.small);
         self.layer.write_text(
-            format!("{} - {}", event.start_date.format(DATE_FORMAT), end_date),
-            &self.font_regular,
+            format_period(&event.start_date, event.end_date.as_ref()),
+            &self.font_thin,
         );
 
         self.layer.end_text_section();
@@ -457,25 +541,57 @@ impl Pdf {
 
         let offset: Mm = offset_x.into();
 
+        let right_width = self.event_summary_width();
+        let summary_x: Pt = (offset + EVENT_SUMMARY_OFFSET).into();
+        let mut baseline = pos_y - EVENT_TEXT_OFFSET;
         self.layer
-            .set_text_cursor(offset + Mm(7.), (pos_y - Pt(20.)).into());
-        self.layer.set_font(&self.font_regular, 9.0);
+            .set_text_cursor(summary_x.into(), baseline.into());
+        self.layer.set_font(&self.font_regular, sizes.small);
+        let mut links = vec![];
         if let Some(summary) = &event.summary {
-            self.write_bounded(summary, 36);
+            links = self.write_markdown(
+                summary,
+                (summary_x, baseline),
+                right_width,
+                &self.font_regular,
+                sizes.small,
+            );
+            baseline -= Pt(self.theme.line_heights.text)
+                * self.markdown_lines(summary, right_width, &self.font_regular, sizes.small) as f64;
         }
 
+        self.layer.end_text_section();
+
         if !event.highlights.is_empty() {
-            self.layer.add_line_break();
-            self.layer.set_font(&self.font_bold, 9.0);
-            self.write_bounded(&event.highlights.join("  -  "), 35)
+            baseline -= Pt(self.theme.line_heights.text);
+            let colors = (self.primary_color.clone(), self.secondary_color.clone());
+            links.extend(self.write_highlights(
+                &event.highlights,
+                (summary_x, baseline),
+                right_width,
+                &self.font_regular,
+                sizes.small,
+                &colors,
+            ));
         }
-        self.layer.end_text_section();
+        self.links.extend(links);
     }
 
-    fn social_qr_code(&self) -> Result<(), Box<dyn Error>> {
-        if let Some(url) = &self.resume.basics.website {
+    /// Draws the QR code of the website at the bottom of the left column and
+    /// returns its height.
+    fn social_qr_code(&mut self) -> Result<Pt, Box<dyn Error>> {
+        let mut height = Pt(0.);
+        if let Some(url) = self.resume.basics.website.clone() {
             debug!("Generating QRCode for: {:?}", url);
-            let qrcode = image::qrcode(url, 220, image::to_rgb(self.primary_color.clone()))?;
+            let qrcode = image::qrcode(&url, 220, image::to_rgb(self.primary_color.clone()))?;
+            let size = qrcode.image.width.into_pt(DPI);
+            height = size;
+            self.links.push(Link {
+                page: self.page,
+                lower_left: (Pt(0.), Pt(0.)),
+                upper_right: (size, size),
+                uri: url,
+            });
             qrcode.add_to_layer(
                 self.layer.clone(),
                 ImageTransform {
@@ -488,7 +604,92 @@ impl Pdf {
                 },
             );
         }
-        Ok(())
+        Ok(height)
+    }
+
+    /// Makes the `width` wide text of `lines` lines starting at `origin`
+    /// clickable, `origin` being the start of its first baseline.
+    fn link_text(
+        &mut self,
+        uri: String,
+        origin: (Pt, Pt),
+        width: Pt,
+        lines: usize,
+        font_size: f64,
+    ) {
+        let (offset_x, baseline) = origin;
+        let height = Pt(self.theme.line_heights.text * (lines - 1) as f64);
+        self.links.push(Link {
+            page: self.page,
+            lower_left: (offset_x, baseline - height - Pt(font_size / 4.)),
+            upper_right: (offset_x + width, baseline + Pt(font_size)),
+            uri,
+        });
+    }
+
+    fn page_width(&self) -> Mm {
+        self.options.paper.width
+    }
+
+    fn page_height(&self) -> Mm {
+        self.options.paper.height
+    }
+
+    /// Width of the left column, nil when the template has none.
+    fn left_column_width(&self) -> Mm {
+        if self.options.template.has_left_column() {
+            Mm(self.theme.columns.left_width)
+        } else {
+            Mm(0.)
+        }
+    }
+
+    fn padding(&self) -> Mm {
+        Mm(self.theme.columns.padding)
+    }
+
+    /// Space between the main column and the edges of its area, the page
+    /// margin when it spans the whole page.
+    fn column_margin(&self) -> Mm {
+        if self.options.template.has_left_column() {
+            self.padding()
+        } else {
+            self.page_margin().into()
+        }
+    }
+
+    fn right_column_offset(&self) -> Mm {
+        self.left_column_width() + self.column_margin()
+    }
+
+    fn right_column_width(&self) -> Mm {
+        self.page_width() - self.right_column_offset() - self.column_margin()
+    }
+
+    /// Horizontal position of the timeline line, centred in the right column.
+    fn timeline_offset(&self) -> Mm {
+        let left_column_width = self.left_column_width();
+        left_column_width + (self.page_width() - left_column_width) / 2.
+    }
+
+    /// Width of the institution, role and period, left of the timeline.
+    fn event_label_width(&self) -> Mm {
+        self.timeline_offset() - self.right_column_offset() - EVENT_GUTTER
+    }
+
+    /// Width of the summary and highlights, right of the timeline.
+    fn event_summary_width(&self) -> Mm {
+        self.page_width() - self.timeline_offset() - EVENT_SUMMARY_OFFSET - self.column_margin()
+    }
+
+    fn page_margin(&self) -> Pt {
+        Pt(self.theme.spacing.page_margin)
+    }
+
+    /// Top of the writable area of a page.
+    fn content_top(&self) -> Pt {
+        let height: Pt = self.page_height().into();
+        height - self.page_margin()
     }
 
     fn draw_left_background(&self) {
@@ -496,8 +697,8 @@ impl Pdf {
             points: shape::rectangle_points(
                 Pt(0.0),
                 Pt(0.0),
-                LEFT_COLUMN_SIZE.into(),
-                DOC_HEIGHT.into(),
+                self.left_column_width().into(),
+                self.page_height().into(),
             ),
             is_closed: true,
             has_fill: true,
@@ -509,3 +710,12 @@ impl Pdf {
         self.layer.add_shape(line);
     }
 }
+
+impl From<theme::Color> for Color {
+    fn from(color: theme::Color) -> Self {
+        match color {
+            theme::Color::Rgb(r, g, b) => Color::Rgb(Rgb::new(r, g, b, None)),
+            theme::Color::Cmyk(c, m, y, k) => Color::Cmyk(Cmyk::new(c, m, y, k, None)),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use printpdf::Mm;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Page size, in portrait orientation unless turned with `landscape`.
+#[derive(Debug, Clone, Copy)]
+pub struct Paper {
+    pub width: Mm,
+    pub height: Mm,
+}
+
+#[derive(Debug)]
+pub struct UnknownPaperError {
+    paper: String,
+}
+
+impl fmt::Display for UnknownPaperError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown paper: {}, expected a4, letter, legal or <width>x<height> in mm",
+            self.paper
+        )
+    }
+}
+
+impl Error for UnknownPaperError {}
+
+impl Paper {
+    pub const A4: Paper = Paper {
+        width: Mm(210.),
+        height: Mm(297.),
+    };
+    pub const LETTER: Paper = Paper {
+        width: Mm(215.9),
+        height: Mm(279.4),
+    };
+    pub const LEGAL: Paper = Paper {
+        width: Mm(215.9),
+        height: Mm(355.6),
+    };
+
+    pub fn landscape(self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl Default for Paper {
+    fn default() -> Self {
+        Paper::A4
+    }
+}
+
+impl FromStr for Paper {
+    type Err = UnknownPaperError;
+
+    fn from_str(paper: &str) -> Result<Self, Self::Err> {
+        let unknown = || UnknownPaperError {
+            paper: paper.to_string(),
+        };
+        match paper.to_lowercase().as_str() {
+            "a4" => Ok(Paper::A4),
+            "letter" => Ok(Paper::LETTER),
+            "legal" => Ok(Paper::LEGAL),
+            custom => {
+                let mut sizes = custom.split('x').map(|size| size.trim().parse::<f64>());
+                match (sizes.next(), sizes.next(), sizes.next()) {
+                    (Some(Ok(width)), Some(Ok(height)), None) if width > 0. && height > 0. => {
+                        Ok(Paper {
+                            width: Mm(width),
+                            height: Mm(height),
+                        })
+                    }
+                    _ => Err(unknown()),
+                }
+            }
+        }
+    }
+}
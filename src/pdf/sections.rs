@@ -0,0 +1,292 @@
+use super::highlights::row_height;
+use super::text::wrapped_lines;
+use super::*;
+use crate::entry::Entry;
+use crate::resume::{Interest, Reference};
+
+pub(super) const CHIP_HEIGHT: Pt = Pt(14.);
+pub(super) const CHIP_PADDING: Pt = Pt(4.);
+pub(super) const CHIP_SPACING: Pt = Pt(4.);
+/// Distance between the lines of a chip too wide for a single one.
+const CHIP_LINE_HEIGHT: Pt = Pt(10.);
+
+const QUOTE_INDENT: Mm = Mm(4.);
+
+impl Pdf {
+    pub(super) fn write_awards(&mut self, pos_y: Pt) -> Pt {
+        let entries: Vec<Entry> = self
+            .resume
+            .awards
+            .iter()
+            .cloned()
+            .map(Entry::from)
+            .collect();
+        self.write_entries(AWARDS, &entries, pos_y)
+    }
+
+    pub(super) fn write_projects(&mut self, pos_y: Pt) -> Pt {
+        let entries: Vec<Entry> = self
+            .resume
+            .projects
+            .iter()
+            .cloned()
+            .map(Entry::from)
+            .collect();
+        self.write_entries(PROJECTS, &entries, pos_y)
+    }
+
+    pub(super) fn write_certificates(&mut self, pos_y: Pt) -> Pt {
+        let entries: Vec<Entry> = self
+            .resume
+            .certificates
+            .iter()
+            .cloned()
+            .map(Entry::from)
+            .collect();
+        self.write_entries(CERTIFICATES, &entries, pos_y)
+    }
+
+    pub(super) fn write_publications(&mut self, pos_y: Pt) -> Pt {
+        let entries: Vec<Entry> = self
+            .resume
+            .publications
+            .iter()
+            .cloned()
+            .map(Entry::from)
+            .collect();
+        self.write_entries(PUBLICATIONS, &entries, pos_y)
+    }
+
+    pub(super) fn write_interests(&mut self, pos_y: Pt) -> Pt {
+        if self.resume.interests.is_empty() {
+            return pos_y;
+        }
+        let chips: Vec<(String, bool)> = self
+            .resume
+            .interests
+            .iter()
+            .flat_map(|Interest { name, keywords }| {
+                std::iter::once((name.clone(), true))
+                    .chain(keywords.iter().map(|keyword| (keyword.clone(), false)))
+            })
+            .collect();
+        let texts: Vec<String> = chips.iter().map(|(text, _)| text.clone()).collect();
+        let mut highlighted = chips.iter().map(|(_, highlighted)| *highlighted);
+        let mut pos_y = self.write_section_title(INTERESTS, pos_y);
+        let left: Pt = self.right_column_offset().into();
+        let colors = (self.primary_color.clone(), self.secondary_color.clone());
+
+        let rows = self.chip_rows(&texts, self.right_column_width());
+        rows.iter().enumerate().for_each(|(index, row)| {
+            if index > 0 {
+                pos_y -= CHIP_SPACING;
+            }
+            let height = row_height(row);
+            pos_y = self.reserve(pos_y, height);
+            let mut pos_x = left;
+            row.iter().for_each(|(text, _, width)| {
+                let highlighted = highlighted.next().unwrap_or_default();
+                self.write_chip(text, highlighted, pos_x, pos_y, *width, &colors);
+                pos_x += *width + CHIP_SPACING;
+            });
+            pos_y -= height;
+        });
+        pos_y - Pt(self.theme.spacing.entry * 2.)
+    }
+
+    pub(super) fn write_references(&mut self, pos_y: Pt) -> Pt {
+        if self.resume.references.is_empty() {
+            return pos_y;
+        }
+        let references: Vec<Reference> = self.resume.references.to_vec();
+        let mut pos_y = self.write_section_title(REFERENCES, pos_y);
+        let line_height = self.theme.line_heights.text;
+        let font_size = self.theme.font_sizes.small;
+        let width = self.right_column_width() - QUOTE_INDENT;
+
+        references.iter().for_each(|Reference { name, reference }| {
+            let quote = format!("\u{201c}{}\u{201d}", reference);
+            let lines = wrapped_lines(&quote, width, &self.font_italic, font_size) + 1;
+            let height = Pt(lines as f64 * line_height);
+            pos_y = self.reserve(pos_y, height);
+
+            let bar = Line {
+                points: shape::rectangle_points(
+                    self.right_column_offset().into(),
+                    pos_y - height,
+                    Pt(2.),
+                    height,
+                ),
+                is_closed: true,
+                has_fill: true,
+                has_stroke: false,
+                is_clipping_path: false,
+            };
+            self.layer.set_fill_color(self.primary_color.clone());
+            self.layer.add_shape(bar);
+
+            self.layer.begin_text_section();
+            self.layer.set_line_height(line_height);
+            self.layer.set_text_cursor(
+                self.right_column_offset() + QUOTE_INDENT,
+                (pos_y - Pt(font_size)).into(),
+            );
+            self.layer.set_font(&self.font_italic, font_size);
+            self.write_bounded(&quote, width, &self.font_italic, font_size);
+            self.layer.set_font(&self.font_bold, font_size);
+            self.layer
+                .write_text(format!("\u{2014} {}", name), &self.font_bold);
+            self.layer.end_text_section();
+
+            pos_y -= height + Pt(self.theme.spacing.entry);
+        });
+        pos_y
+    }
+
+    /// Writes a titled list of entries in the right column, omitting the
+    /// section when there is nothing to show.
+    fn write_entries(&mut self, title: &str, entries: &[Entry], pos_y: Pt) -> Pt {
+        if entries.is_empty() {
+            return pos_y;
+        }
+        let mut pos_y = self.write_section_title(title, pos_y);
+        let line_height = self.theme.line_heights.text;
+        let sizes = self.theme.font_sizes.clone();
+        let width = self.right_column_width();
+
+        entries.iter().for_each(|entry| {
+            let lines = wrapped_lines(&entry.title, width, &self.font_bold, sizes.text)
+                + 1
+                + entry
+                    .summary
+                    .as_ref()
+                    .map(|summary| {
+                        self.markdown_lines(summary, width, &self.font_regular, sizes.small)
+                    })
+                    .unwrap_or(0);
+            let mut height = Pt(lines as f64 * line_height);
+            if !entry.highlights.is_empty() {
+                height += self.highlights_height(
+                    &entry.highlights,
+                    width,
+                    &self.font_regular,
+                    sizes.small,
+                );
+            }
+            pos_y = self.reserve(pos_y, height);
+
+            if let Some(website) = &entry.website {
+                let lines = wrapped_lines(&entry.title, width, &self.font_bold, sizes.text);
+                let title_width = match lines {
+                    1 => self.font_bold.text_width(&entry.title, sizes.text),
+                    _ => width.into(),
+                };
+                self.link_text(
+                    website.clone(),
+                    (self.right_column_offset().into(), pos_y - Pt(sizes.text)),
+                    title_width,
+                    lines,
+                    sizes.text,
+                );
+            }
+
+            self.layer.set_fill_color(self.primary_color.clone());
+            self.layer.begin_text_section();
+            self.layer.set_line_height(line_height);
+            self.layer
+                .set_text_cursor(self.right_column_offset(), (pos_y - Pt(sizes.text)).into());
+            self.layer.set_font(&self.font_bold, sizes.text);
+            self.write_bounded(&entry.title, width, &self.font_bold, sizes.text);
+            self.layer.set_font(&self.font_thin, sizes.small);
+            self.layer.write_text(&entry.subtitle, &self.font_thin);
+            self.layer.add_line_break();
+            let offset_x = self.right_column_offset().into();
+            let mut baseline = pos_y
+                - Pt(sizes.text)
+                - Pt(line_height
+                    * (wrapped_lines(&entry.title, width, &self.font_bold, sizes.text) + 1) as f64);
+            let mut links = vec![];
+            if let Some(summary) = &entry.summary {
+                self.layer.set_font(&self.font_regular, sizes.small);
+                links = self.write_markdown(
+                    summary,
+                    (offset_x, baseline),
+                    width,
+                    &self.font_regular,
+                    sizes.small,
+                );
+                baseline -= Pt(line_height
+                    * self.markdown_lines(summary, width, &self.font_regular, sizes.small) as f64);
+            }
+            self.layer.end_text_section();
+            if !entry.highlights.is_empty() {
+                let colors = (self.primary_color.clone(), self.secondary_color.clone());
+                links.extend(self.write_highlights(
+                    &entry.highlights,
+                    (offset_x, baseline),
+                    width,
+                    &self.font_regular,
+                    sizes.small,
+                    &colors,
+                ));
+            }
+            self.links.extend(links);
+
+            pos_y -= height + Pt(self.theme.spacing.entry);
+        });
+        pos_y
+    }
+
+    /// Draws a chip whose top left corner is at `pos_x` and `pos_y`, `colors`
+    /// being its outline and text color and the background it is drawn on.
+    pub(super) fn write_chip(
+        &self,
+        text: &str,
+        highlighted: bool,
+        pos_x: Pt,
+        pos_y: Pt,
+        width: Pt,
+        colors: &(Color, Color),
+    ) {
+        let height = chip_height(text);
+        let chip = Line {
+            points: shape::rectangle_points(pos_x, pos_y - height, width, height),
+            is_closed: true,
+            has_fill: highlighted,
+            has_stroke: true,
+            is_clipping_path: false,
+        };
+        let (color, background) = colors;
+        self.layer.set_outline_color(color.clone());
+        self.layer.set_fill_color(color.clone());
+        self.layer.add_shape(chip);
+
+        let text_color = if highlighted {
+            background.clone()
+        } else {
+            color.clone()
+        };
+        self.layer.set_fill_color(text_color);
+        self.layer.begin_text_section();
+        self.layer
+            .set_font(&self.font_regular, self.theme.font_sizes.small);
+        self.layer.set_text_cursor(
+            (pos_x + CHIP_PADDING).into(),
+            (pos_y - CHIP_HEIGHT + Pt(4.)).into(),
+        );
+        self.layer.set_line_height(CHIP_LINE_HEIGHT.0);
+        text.lines().enumerate().for_each(|(index, line)| {
+            if index > 0 {
+                self.layer.add_line_break();
+            }
+            self.layer.write_text(line, &self.font_regular);
+        });
+        self.layer.end_text_section();
+    }
+}
+
+/// Height of a chip holding `text`, one line more for each line break.
+pub(super) fn chip_height(text: &str) -> Pt {
+    let lines = text.lines().count().max(1);
+    CHIP_HEIGHT + CHIP_LINE_HEIGHT * (lines - 1) as f64
+}
@@ -22,3 +22,36 @@ pub fn rectangle_points(offset_x: Pt, offset_y: Pt, width: Pt, height: Pt) -> Ve
         (bottom_left_pt, false),
     ]
 }
+
+pub fn diamond_points(center_x: Pt, center_y: Pt, radius: Pt) -> Vec<(Point, bool)> {
+    vec![
+        (
+            Point {
+                x: center_x,
+                y: center_y + radius,
+            },
+            false,
+        ),
+        (
+            Point {
+                x: center_x + radius,
+                y: center_y,
+            },
+            false,
+        ),
+        (
+            Point {
+                x: center_x,
+                y: center_y - radius,
+            },
+            false,
+        ),
+        (
+            Point {
+                x: center_x - radius,
+                y: center_y,
+            },
+            false,
+        ),
+    ]
+}
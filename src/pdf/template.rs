@@ -0,0 +1,367 @@
+use super::AboutPlacement;
+use crate::theme::Theme;
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Layout of the pages: the regions they are made of and the sections each
+/// region holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Template {
+    /// Picture, contact details and skills in a coloured left column, next
+    /// to the timeline
+    #[default]
+    Sidebar,
+    /// A single column headed by the name and contact details
+    Classic,
+    /// The single column with tighter sizes and spacing, leaving the
+    /// interests and references out to fit on one page
+    Compact,
+}
+
+/// Area of the page sections are written in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Region {
+    /// Coloured column on the left of the first page
+    Left,
+    /// Column flowing over as many pages as needed
+    Main,
+}
+
+/// Part of the resume written as a whole in a region.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Section {
+    Bio,
+    About,
+    Info,
+    Social,
+    Languages,
+    Skills,
+    Timeline,
+    Projects,
+    Awards,
+    Certificates,
+    Publications,
+    Interests,
+    References,
+}
+
+const SECTIONS: [Section; 13] = [
+    Section::Bio,
+    Section::About,
+    Section::Info,
+    Section::Social,
+    Section::Languages,
+    Section::Skills,
+    Section::Timeline,
+    Section::Projects,
+    Section::Awards,
+    Section::Certificates,
+    Section::Publications,
+    Section::Interests,
+    Section::References,
+];
+
+/// Sections of the regions, in the order they are written, replacing those
+/// of the template for the regions given.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Layout {
+    pub left: Option<Vec<Section>>,
+    pub main: Option<Vec<Section>>,
+}
+
+#[derive(Debug)]
+pub struct UnknownTemplateError {
+    template: String,
+}
+
+impl fmt::Display for UnknownTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown template: {}, expected sidebar, classic or compact",
+            self.template
+        )
+    }
+}
+
+impl Error for UnknownTemplateError {}
+
+#[derive(Debug)]
+pub struct UnknownSectionError {
+    section: String,
+}
+
+impl fmt::Display for UnknownSectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = SECTIONS.iter().map(|section| section.name()).collect();
+        write!(
+            f,
+            "Unknown section: {}, expected one of {}",
+            self.section,
+            names.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownSectionError {}
+
+#[derive(Debug)]
+pub struct SectionPlacementError {
+    section: Section,
+    template: Template,
+}
+
+impl fmt::Display for SectionPlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The {} section cannot be placed in the left column of the {:?} template",
+            self.section.name(),
+            self.template
+        )
+    }
+}
+
+impl Error for SectionPlacementError {}
+
+#[derive(Debug)]
+pub struct DuplicateSectionError {
+    section: Section,
+}
+
+impl fmt::Display for DuplicateSectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The {} section appears more than once in the left and main sections",
+            self.section.name()
+        )
+    }
+}
+
+impl Error for DuplicateSectionError {}
+
+impl FromStr for Template {
+    type Err = UnknownTemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        match template.to_lowercase().as_str() {
+            "sidebar" => Ok(Template::Sidebar),
+            "classic" => Ok(Template::Classic),
+            "compact" => Ok(Template::Compact),
+            _ => Err(UnknownTemplateError {
+                template: template.to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<String> for Template {
+    type Error = UnknownTemplateError;
+
+    fn try_from(template: String) -> Result<Self, Self::Error> {
+        template.parse()
+    }
+}
+
+impl FromStr for Section {
+    type Err = UnknownSectionError;
+
+    fn from_str(section: &str) -> Result<Self, Self::Err> {
+        let name = section.trim().to_lowercase();
+        SECTIONS
+            .iter()
+            .find(|known| known.name() == name)
+            .copied()
+            .ok_or_else(|| UnknownSectionError {
+                section: section.to_string(),
+            })
+    }
+}
+
+impl TryFrom<String> for Section {
+    type Error = UnknownSectionError;
+
+    fn try_from(section: String) -> Result<Self, Self::Error> {
+        section.parse()
+    }
+}
+
+impl Section {
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Bio => "bio",
+            Section::About => "about",
+            Section::Info => "info",
+            Section::Social => "social",
+            Section::Languages => "languages",
+            Section::Skills => "skills",
+            Section::Timeline => "timeline",
+            Section::Projects => "projects",
+            Section::Awards => "awards",
+            Section::Certificates => "certificates",
+            Section::Publications => "publications",
+            Section::Interests => "interests",
+            Section::References => "references",
+        }
+    }
+
+    /// Whether the section can be written in the left column.
+    fn fits_left(self) -> bool {
+        matches!(
+            self,
+            Section::Bio
+                | Section::About
+                | Section::Info
+                | Section::Social
+                | Section::Languages
+                | Section::Skills
+        )
+    }
+}
+
+impl Layout {
+    /// Fills the regions missing from `self` with those of `other`.
+    pub fn or(self, other: Layout) -> Self {
+        Self {
+            left: self.left.or(other.left),
+            main: self.main.or(other.main),
+        }
+    }
+
+    /// Sections of `region`, those of `template` unless given.
+    pub fn sections(
+        &self,
+        template: Template,
+        region: Region,
+        about: AboutPlacement,
+    ) -> Vec<Section> {
+        let sections = match region {
+            Region::Left => &self.left,
+            Region::Main => &self.main,
+        };
+        sections
+            .clone()
+            .unwrap_or_else(|| template.sections(region, about))
+    }
+
+    /// Makes sure the left column of `template` can hold the sections given
+    /// for it, and that no section is written twice.
+    pub fn check(&self, template: Template, about: AboutPlacement) -> Result<(), Box<dyn Error>> {
+        let misplaced = self
+            .left
+            .iter()
+            .flatten()
+            .find(|section| !template.has_left_column() || !section.fits_left());
+        if let Some(section) = misplaced {
+            return Err(SectionPlacementError {
+                section: *section,
+                template,
+            }
+            .into());
+        }
+
+        let mut sections = self.sections(template, Region::Left, about);
+        sections.extend(self.sections(template, Region::Main, about));
+        let duplicate = sections
+            .iter()
+            .enumerate()
+            .find(|(index, section)| sections[..*index].contains(section));
+        match duplicate {
+            Some((_, section)) => Err(DuplicateSectionError { section: *section }.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Template {
+    pub fn has_left_column(self) -> bool {
+        self == Template::Sidebar
+    }
+
+    /// Sections of `region`, in the order they are written. Only the sidebar
+    /// places the summary according to `about`.
+    pub fn sections(self, region: Region, about: AboutPlacement) -> Vec<Section> {
+        use Section::*;
+        match (self, region) {
+            (Template::Sidebar, Region::Left) => match about {
+                AboutPlacement::Left => vec![Bio, About, Info, Social, Languages, Skills],
+                AboutPlacement::Right => vec![Bio, Info, Social, Languages, Skills],
+            },
+            (Template::Sidebar, Region::Main) => {
+                let sections = vec![
+                    Timeline,
+                    Projects,
+                    Awards,
+                    Certificates,
+                    Publications,
+                    Interests,
+                    References,
+                ];
+                match about {
+                    AboutPlacement::Left => sections,
+                    AboutPlacement::Right => std::iter::once(About).chain(sections).collect(),
+                }
+            }
+            (_, Region::Left) => vec![],
+            (Template::Classic, Region::Main) => vec![
+                Bio,
+                Info,
+                Social,
+                About,
+                Timeline,
+                Projects,
+                Skills,
+                Languages,
+                Awards,
+                Certificates,
+                Publications,
+                Interests,
+                References,
+            ],
+            (Template::Compact, Region::Main) => vec![
+                Bio,
+                Info,
+                Social,
+                About,
+                Timeline,
+                Skills,
+                Languages,
+                Projects,
+                Awards,
+                Certificates,
+                Publications,
+            ],
+        }
+    }
+
+    /// Adjusts the sizes and spacing of `theme` to the template.
+    pub fn theme(self, mut theme: Theme) -> Theme {
+        if self == Template::Compact {
+            let sizes = &mut theme.font_sizes;
+            sizes.name -= 4.;
+            sizes.heading -= 2.;
+            sizes.title -= 2.;
+            sizes.text -= 1.;
+            sizes.small -= 1.;
+            let line_heights = &mut theme.line_heights;
+            line_heights.name *= 0.8;
+            line_heights.heading *= 0.8;
+            line_heights.list *= 0.8;
+            line_heights.skills *= 0.8;
+            line_heights.text *= 0.85;
+            let spacing = &mut theme.spacing;
+            spacing.page_margin *= 0.75;
+            spacing.event *= 0.5;
+            spacing.entry *= 0.5;
+            spacing.section *= 0.6;
+        }
+        theme
+    }
+}
@@ -1,4 +1,7 @@
-use super::{shape, Pdf, RIGHT_COLUMN_HEIGHT};
+use super::font::Font;
+use super::inline::{self, Span};
+use super::link::Link;
+use super::{shape, Pdf};
 use printpdf::*;
 
 use std::error::Error;
@@ -7,7 +10,7 @@ impl Pdf {
     pub(super) fn write_social_icon(
         &self,
         name: &str,
-        font_size: i64,
+        font_size: f64,
     ) -> Result<(), Box<dyn Error>> {
         let color = match name {
             "twitter" => Color::Rgb(Rgb::new(0.11, 0.63, 0.95, None)),
@@ -23,11 +26,12 @@ impl Pdf {
     pub(super) fn write_underlined_text(
         &self,
         text: &str,
-        font_size: i64,
+        font_size: f64,
         offset_x: Mm,
         offset_y: Mm,
+        width: Mm,
     ) {
-        self.layer.set_font(&self.font_bold, font_size as f64);
+        self.layer.set_font(&self.font_bold, font_size);
         self.layer.set_text_cursor(offset_x, offset_y);
         self.layer.write_text(text, &self.font_bold);
         let offset_y_pt: Pt = offset_y.into();
@@ -35,8 +39,8 @@ impl Pdf {
         let line = Line {
             points: shape::rectangle_points(
                 offset_x.into(),
-                offset_y_pt - (Pt(font_size as f64) + stroke_size),
-                (RIGHT_COLUMN_HEIGHT - offset_x - offset_x).into(),
+                offset_y_pt - (Pt(font_size) + stroke_size),
+                width.into(),
                 stroke_size,
             ),
             is_closed: true,
@@ -47,10 +51,274 @@ impl Pdf {
         self.layer.add_shape(line);
     }
 
-    pub(super) fn write_bounded(&self, text: &str, width: usize) {
-        textwrap::fill(text, width).split('\n').for_each(|line| {
-            self.layer.write_text(line, &self.font_regular);
+    pub(super) fn write_bounded(&self, text: &str, width: Mm, font: &Font, font_size: f64) {
+        wrap(text, width, font, font_size).iter().for_each(|line| {
+            self.layer.write_text(line, font);
             self.layer.add_line_break();
         });
     }
 }
+
+impl Pdf {
+    /// Writes `text` like `write_bounded`, rendering its inline Markdown with
+    /// the bold and italic fonts, and returns the links it contains. `origin`
+    /// is the start of the first baseline.
+    pub(super) fn write_markdown(
+        &self,
+        text: &str,
+        origin: (Pt, Pt),
+        width: Mm,
+        font: &Font,
+        font_size: f64,
+    ) -> Vec<Link> {
+        let spans = inline::parse(text);
+        let (offset_x, baseline) = origin;
+        let line_height = Pt(self.theme.line_heights.text);
+        let mut links: Vec<Link> = vec![];
+
+        self.wrap_spans(&spans, width, font, font_size)
+            .iter()
+            .enumerate()
+            .for_each(|(number, line)| {
+                let baseline = baseline - line_height * number as f64;
+                let mut pos_x = offset_x;
+                line.iter().for_each(|(span, text)| {
+                    let span = &spans[*span];
+                    let span_font = self.span_font(span, font);
+                    let text_width = span_font.text_width(text, font_size);
+                    self.layer.set_font(span_font, font_size);
+                    self.layer.write_text(text, span_font);
+
+                    if let Some(uri) = &span.link {
+                        match links.last_mut() {
+                            Some(link)
+                                if &link.uri == uri
+                                    && link.upper_right.1 == baseline + Pt(font_size)
+                                    && link.upper_right.0 == pos_x =>
+                            {
+                                link.upper_right.0 = pos_x + text_width;
+                            }
+                            _ => links.push(Link {
+                                page: self.page,
+                                lower_left: (pos_x, baseline - Pt(font_size / 4.)),
+                                upper_right: (pos_x + text_width, baseline + Pt(font_size)),
+                                uri: uri.clone(),
+                            }),
+                        }
+                    }
+                    pos_x += text_width;
+                });
+                self.layer.add_line_break();
+            });
+        self.layer.set_font(font, font_size);
+        links
+    }
+
+    /// Number of lines `write_markdown` needs for `text`.
+    pub(super) fn markdown_lines(
+        &self,
+        text: &str,
+        width: Mm,
+        font: &Font,
+        font_size: f64,
+    ) -> usize {
+        self.wrap_spans(&inline::parse(text), width, font, font_size)
+            .len()
+    }
+
+    /// Font of `span` inside text written with `font`.
+    fn span_font<'a>(&'a self, span: &Span, font: &'a Font) -> &'a Font {
+        if span.italic {
+            &self.font_italic
+        } else if span.bold {
+            &self.font_bold
+        } else {
+            font
+        }
+    }
+
+    /// Same as `wrap`, each line being made of pieces of text tagged with the
+    /// index of their span.
+    fn wrap_spans(
+        &self,
+        spans: &[Span],
+        width: Mm,
+        font: &Font,
+        font_size: f64,
+    ) -> Vec<Vec<Piece>> {
+        let width: Pt = width.into();
+        let piece_width = |(span, text): &Piece| {
+            self.span_font(&spans[*span], font)
+                .text_width(text, font_size)
+        };
+        let mut lines = vec![];
+
+        paragraphs(spans).into_iter().for_each(|paragraph| {
+            let mut line: Vec<Piece> = vec![];
+            let mut line_width = Pt(0.);
+            paragraph.into_iter().for_each(|(space, mut word)| {
+                let space = (space, " ".to_string());
+                let space_width = piece_width(&space);
+                let mut word_width: Pt = word.iter().map(piece_width).fold(Pt(0.), |a, b| a + b);
+                if !line.is_empty() && line_width + space_width + word_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = Pt(0.);
+                }
+                while line.is_empty() && word_width > width && word_chars(&word) > 1 {
+                    let mut split = 1;
+                    while split + 1 < word_chars(&word)
+                        && split_word(&word, split + 1)
+                            .0
+                            .iter()
+                            .map(piece_width)
+                            .fold(Pt(0.), |a, b| a + b)
+                            <= width
+                    {
+                        split += 1;
+                    }
+                    let (head, tail) = split_word(&word, split);
+                    lines.push(head);
+                    word = tail;
+                    word_width = word.iter().map(piece_width).fold(Pt(0.), |a, b| a + b);
+                }
+                if !line.is_empty() {
+                    line_width += space_width;
+                    push_piece(&mut line, space);
+                }
+                line_width += word_width;
+                word.into_iter()
+                    .for_each(|piece| push_piece(&mut line, piece));
+            });
+            lines.push(line);
+        });
+
+        if lines.is_empty() {
+            lines.push(vec![]);
+        }
+        lines
+    }
+}
+
+/// Text written with a single font, tagged with the index of its span.
+type Piece = (usize, String);
+
+/// Word made of pieces, along with the span of the space before it.
+type Word = (usize, Vec<Piece>);
+
+/// Splits spans into paragraphs, on line feeds, and words, on whitespace.
+fn paragraphs(spans: &[Span]) -> Vec<Vec<Word>> {
+    let mut paragraphs = vec![];
+    let mut paragraph = vec![];
+    let mut word: Word = (0, vec![]);
+
+    spans.iter().enumerate().for_each(|(index, span)| {
+        span.text.chars().for_each(|c| {
+            if c.is_whitespace() {
+                if !word.1.is_empty() {
+                    paragraph.push(std::mem::replace(&mut word, (index, vec![])));
+                } else {
+                    word.0 = index;
+                }
+                if c == '\n' {
+                    paragraphs.push(std::mem::take(&mut paragraph));
+                }
+            } else {
+                match word.1.last_mut() {
+                    Some((span, text)) if *span == index => text.push(c),
+                    _ => word.1.push((index, c.to_string())),
+                }
+            }
+        });
+    });
+    if !word.1.is_empty() {
+        paragraph.push(word);
+    }
+    paragraphs.push(paragraph);
+    paragraphs
+}
+
+fn push_piece(line: &mut Vec<Piece>, piece: Piece) {
+    match line.last_mut() {
+        Some((span, text)) if *span == piece.0 => text.push_str(&piece.1),
+        _ => line.push(piece),
+    }
+}
+
+fn word_chars(word: &[Piece]) -> usize {
+    word.iter().map(|(_, text)| text.chars().count()).sum()
+}
+
+/// Splits `word` after its `split` first characters.
+fn split_word(word: &[Piece], mut split: usize) -> (Vec<Piece>, Vec<Piece>) {
+    let mut head = vec![];
+    let mut tail = vec![];
+    word.iter().for_each(|(span, text)| {
+        let count = text.chars().count();
+        if split >= count {
+            head.push((*span, text.clone()));
+            split -= count;
+        } else if split == 0 {
+            tail.push((*span, text.clone()));
+        } else {
+            let (index, _) = text.char_indices().nth(split).unwrap_or((text.len(), ' '));
+            head.push((*span, text[..index].to_string()));
+            tail.push((*span, text[index..].to_string()));
+            split = 0;
+        }
+    });
+    (head, tail)
+}
+
+/// Number of lines `write_bounded` needs for `text`.
+pub(super) fn wrapped_lines(text: &str, width: Mm, font: &Font, font_size: f64) -> usize {
+    wrap(text, width, font, font_size).len()
+}
+
+/// Splits `text` into lines no wider than `width` once written with `font`
+/// at `font_size`, breaking words only when they do not fit on a line.
+pub(super) fn wrap(text: &str, width: Mm, font: &Font, font_size: f64) -> Vec<String> {
+    let width: Pt = width.into();
+    let space = font.text_width(" ", font_size);
+    let mut lines = vec![];
+
+    text.lines().for_each(|paragraph| {
+        let mut line = String::new();
+        let mut line_width = Pt(0.);
+        paragraph.split_whitespace().for_each(|mut word| {
+            let mut word_width = font.text_width(word, font_size);
+            if !line.is_empty() && line_width + space + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = Pt(0.);
+            }
+            while line.is_empty() && word_width > width {
+                let split = word
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .skip(1)
+                    .take_while(|&i| font.text_width(&word[..i], font_size) <= width)
+                    .last()
+                    .or_else(|| word.char_indices().nth(1).map(|(i, _)| i));
+                match split {
+                    Some(split) => {
+                        lines.push(word[..split].to_string());
+                        word = &word[split..];
+                        word_width = font.text_width(word, font_size);
+                    }
+                    None => break,
+                }
+            }
+            if !line.is_empty() {
+                line.push(' ');
+                line_width += space;
+            }
+            line.push_str(word);
+            line_width += word_width;
+        });
+        lines.push(line);
+    });
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
@@ -0,0 +1,360 @@
+use super::{Item, PreviewError};
+use owned_ttf_parser::{AsFaceRef, GlyphId, OwnedFace};
+use printpdf::lopdf::{Dictionary, Document, Object, ObjectId};
+use std::collections::HashMap;
+use std::error::Error;
+use tiny_skia::{ColorU8, IntSize, PathBuilder, Pixmap, Rect, Transform};
+
+/// Colors and transformation saved and restored by `q` and `Q`.
+#[derive(Clone)]
+struct GraphicsState {
+    ctm: Transform,
+    fill: ColorU8,
+    stroke: ColorU8,
+    line_width: f32,
+}
+
+/// Font and positions of a text section.
+struct TextState {
+    matrix: Transform,
+    line_matrix: Transform,
+    leading: f32,
+    font: Option<usize>,
+    size: f32,
+}
+
+/// Items drawn by the content stream of a page, along with the fonts they
+/// use.
+pub struct Content {
+    pub items: Vec<Item>,
+    pub fonts: Vec<OwnedFace>,
+    images: HashMap<Vec<u8>, Pixmap>,
+    font_names: HashMap<Vec<u8>, usize>,
+}
+
+impl Content {
+    pub fn from_page(document: &Document, page_id: ObjectId) -> Result<Self, Box<dyn Error>> {
+        let mut content = Self {
+            items: vec![],
+            fonts: vec![],
+            images: HashMap::new(),
+            font_names: HashMap::new(),
+        };
+        let (resources, resource_ids) = document.get_page_resources(page_id);
+        let resources = resources.into_iter().chain(
+            resource_ids
+                .into_iter()
+                .filter_map(|id| document.get_dictionary(id).ok()),
+        );
+        for resources in resources {
+            for (name, font) in resource_entries(document, resources, b"Font") {
+                match load_font(document, font) {
+                    Ok(face) => {
+                        content.font_names.insert(name.clone(), content.fonts.len());
+                        content.fonts.push(face);
+                    }
+                    Err(err) => warn!("Skipping font {}: {}", String::from_utf8_lossy(name), err),
+                }
+            }
+            for (name, object) in resource_entries(document, resources, b"XObject") {
+                match load_image(document, object) {
+                    Ok(image) => {
+                        content.images.insert(name.clone(), image);
+                    }
+                    Err(err) => warn!("Skipping image {}: {}", String::from_utf8_lossy(name), err),
+                }
+            }
+        }
+        content.interpret(&document.get_and_decode_page_content(page_id)?.operations);
+        Ok(content)
+    }
+
+    fn interpret(&mut self, operations: &[printpdf::lopdf::content::Operation]) {
+        let mut state = GraphicsState {
+            ctm: Transform::identity(),
+            fill: ColorU8::from_rgba(0, 0, 0, 255),
+            stroke: ColorU8::from_rgba(0, 0, 0, 255),
+            line_width: 1.,
+        };
+        let mut saved = vec![];
+        let mut text = TextState {
+            matrix: Transform::identity(),
+            line_matrix: Transform::identity(),
+            leading: 0.,
+            font: None,
+            size: 0.,
+        };
+        let mut path = PathBuilder::new();
+
+        for operation in operations {
+            let operands = &operation.operands;
+            let number = |i: usize| {
+                operands
+                    .get(i)
+                    .and_then(|operand| operand.as_float().ok())
+                    .unwrap_or(0.) as f32
+            };
+            match operation.operator.as_str() {
+                "q" => saved.push(state.clone()),
+                "Q" => state = saved.pop().unwrap_or(state),
+                "cm" => {
+                    let matrix = Transform::from_row(
+                        number(0),
+                        number(1),
+                        number(2),
+                        number(3),
+                        number(4),
+                        number(5),
+                    );
+                    state.ctm = state.ctm.pre_concat(matrix);
+                }
+                "w" => state.line_width = number(0),
+                "g" => state.fill = gray(number(0)),
+                "G" => state.stroke = gray(number(0)),
+                "rg" => state.fill = rgb(number(0), number(1), number(2)),
+                "RG" => state.stroke = rgb(number(0), number(1), number(2)),
+                "k" => state.fill = cmyk(number(0), number(1), number(2), number(3)),
+                "K" => state.stroke = cmyk(number(0), number(1), number(2), number(3)),
+                "m" => path.move_to(number(0), number(1)),
+                "l" => path.line_to(number(0), number(1)),
+                "c" => path.cubic_to(
+                    number(0),
+                    number(1),
+                    number(2),
+                    number(3),
+                    number(4),
+                    number(5),
+                ),
+                "re" => {
+                    if let Some(rect) = Rect::from_xywh(number(0), number(1), number(2), number(3))
+                    {
+                        path.push_rect(rect);
+                    }
+                }
+                "h" => path.close(),
+                "f" | "F" | "f*" | "S" | "s" | "B" | "B*" | "b" | "b*" | "n" => {
+                    let operator = operation.operator.as_str();
+                    if operator.starts_with('s') || operator.starts_with('b') {
+                        path.close();
+                    }
+                    let fill = match operator {
+                        "f" | "F" | "f*" | "B" | "B*" | "b" | "b*" => Some(state.fill),
+                        _ => None,
+                    };
+                    let stroke = match operator {
+                        "S" | "s" | "B" | "B*" | "b" | "b*" => {
+                            Some((state.stroke, state.line_width))
+                        }
+                        _ => None,
+                    };
+                    if let Some(shape) = std::mem::take(&mut path).finish() {
+                        if fill.is_some() || stroke.is_some() {
+                            self.items.push(Item::Path {
+                                path: shape,
+                                transform: state.ctm,
+                                fill,
+                                stroke,
+                            });
+                        }
+                    }
+                }
+                "BT" => {
+                    text.matrix = Transform::identity();
+                    text.line_matrix = Transform::identity();
+                }
+                "Tf" => {
+                    text.font = operands
+                        .first()
+                        .and_then(|name| name.as_name().ok())
+                        .and_then(|name| self.font_names.get(name))
+                        .copied();
+                    text.size = number(1);
+                }
+                "TL" => text.leading = number(0),
+                "Td" => next_line(&mut text, number(0), number(1)),
+                "TD" => {
+                    text.leading = -number(1);
+                    next_line(&mut text, number(0), number(1));
+                }
+                "T*" => {
+                    let leading = text.leading;
+                    next_line(&mut text, 0., -leading);
+                }
+                "Tm" => {
+                    text.line_matrix = Transform::from_row(
+                        number(0),
+                        number(1),
+                        number(2),
+                        number(3),
+                        number(4),
+                        number(5),
+                    );
+                    text.matrix = text.line_matrix;
+                }
+                "Tj" | "'" => {
+                    if operation.operator == "'" {
+                        let leading = text.leading;
+                        next_line(&mut text, 0., -leading);
+                    }
+                    if let Some(Ok(glyphs)) = operands.first().map(Object::as_str) {
+                        self.show_text(&mut text, &state, glyphs);
+                    }
+                }
+                "TJ" => {
+                    if let Some(Ok(parts)) = operands.first().map(Object::as_array) {
+                        for part in parts {
+                            match part {
+                                Object::String(glyphs, _) => {
+                                    self.show_text(&mut text, &state, glyphs)
+                                }
+                                part => {
+                                    let offset = part.as_float().unwrap_or(0.) as f32;
+                                    text.matrix =
+                                        text.matrix.pre_translate(-offset / 1000. * text.size, 0.);
+                                }
+                            }
+                        }
+                    }
+                }
+                "Do" => {
+                    if let Some(image) = operands
+                        .first()
+                        .and_then(|name| name.as_name().ok())
+                        .and_then(|name| self.images.get(name))
+                    {
+                        self.items.push(Item::Image {
+                            image: image.clone(),
+                            transform: state.ctm,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Draws the glyphs of a `Tj` string, two bytes each, moving the text
+    /// matrix past them.
+    fn show_text(&mut self, text: &mut TextState, state: &GraphicsState, glyphs: &[u8]) {
+        let font = match text.font {
+            Some(font) => font,
+            None => return,
+        };
+        let face = self.fonts[font].as_face_ref();
+        let scale = text.size / f32::from(face.units_per_em().unwrap_or(1000));
+        for bytes in glyphs.chunks(2) {
+            let glyph = match bytes {
+                [high, low] => u16::from_be_bytes([*high, *low]),
+                [byte] => u16::from(*byte),
+                _ => 0,
+            };
+            self.items.push(Item::Glyph {
+                font,
+                glyph,
+                transform: state.ctm.pre_concat(text.matrix).pre_scale(scale, scale),
+                color: state.fill,
+            });
+            let advance = face.glyph_hor_advance(GlyphId(glyph)).unwrap_or(0);
+            text.matrix = text.matrix.pre_translate(f32::from(advance) * scale, 0.);
+        }
+    }
+}
+
+fn next_line(text: &mut TextState, offset_x: f32, offset_y: f32) {
+    text.line_matrix = text.line_matrix.pre_translate(offset_x, offset_y);
+    text.matrix = text.line_matrix;
+}
+
+fn gray(level: f32) -> ColorU8 {
+    rgb(level, level, level)
+}
+
+fn rgb(r: f32, g: f32, b: f32) -> ColorU8 {
+    let channel = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    ColorU8::from_rgba(channel(r), channel(g), channel(b), 255)
+}
+
+fn cmyk(c: f32, m: f32, y: f32, k: f32) -> ColorU8 {
+    rgb(
+        (1. - c) * (1. - k),
+        (1. - m) * (1. - k),
+        (1. - y) * (1. - k),
+    )
+}
+
+/// Named entries of a resource category, such as the fonts or the images.
+fn resource_entries<'a>(
+    document: &'a Document,
+    resources: &'a Dictionary,
+    category: &[u8],
+) -> impl Iterator<Item = (&'a Vec<u8>, &'a Object)> {
+    resources
+        .get(category)
+        .ok()
+        .and_then(|entries| document.dereference(entries).ok())
+        .and_then(|(_, entries)| entries.as_dict().ok())
+        .into_iter()
+        .flat_map(|entries| entries.iter())
+}
+
+fn stream_content(document: &Document, object: &Object) -> Result<Vec<u8>, Box<dyn Error>> {
+    let (_, object) = document.dereference(object)?;
+    let stream = object.as_stream()?;
+    if stream.dict.has(b"Filter") {
+        Ok(stream.decompressed_content()?)
+    } else {
+        Ok(stream.content.clone())
+    }
+}
+
+/// TrueType font embedded for a `Type0` font of the page.
+fn load_font(document: &Document, font: &Object) -> Result<OwnedFace, Box<dyn Error>> {
+    let (_, font) = document.dereference(font)?;
+    let descendant = font
+        .as_dict()?
+        .get_deref(b"DescendantFonts", document)?
+        .as_array()?
+        .first()
+        .ok_or(PreviewError::UnsupportedFont)?;
+    let descriptor = document
+        .dereference(descendant)?
+        .1
+        .as_dict()?
+        .get_deref(b"FontDescriptor", document)?
+        .as_dict()?;
+    let bytes = stream_content(document, descriptor.get(b"FontFile2")?)?;
+    Ok(OwnedFace::from_vec(bytes, 0)?)
+}
+
+/// Uncompressed 8 bits RGB or gray image, as added by printpdf.
+fn load_image(document: &Document, object: &Object) -> Result<Pixmap, Box<dyn Error>> {
+    let (_, object) = document.dereference(object)?;
+    let stream = object.as_stream()?;
+    let dict = &stream.dict;
+    let width = dict.get(b"Width")?.as_i64()? as u32;
+    let height = dict.get(b"Height")?.as_i64()? as u32;
+    let channels = match dict.get(b"ColorSpace")?.as_name()? {
+        b"DeviceRGB" => 3,
+        b"DeviceGray" => 1,
+        _ => return Err(PreviewError::UnsupportedImage.into()),
+    };
+    if dict.has(b"Filter") || dict.get(b"BitsPerComponent")?.as_i64()? != 8 {
+        return Err(PreviewError::UnsupportedImage.into());
+    }
+    let pixels = (width * height) as usize;
+    if stream.content.len() < pixels * channels {
+        return Err(PreviewError::UnsupportedImage.into());
+    }
+    let rgba = stream
+        .content
+        .chunks(channels)
+        .take(pixels)
+        .flat_map(|pixel| match pixel {
+            [r, g, b] => [*r, *g, *b, 255],
+            [gray] => [*gray, *gray, *gray, 255],
+            _ => [0, 0, 0, 255],
+        })
+        .collect();
+    let size = IntSize::from_wh(width, height).ok_or(PreviewError::UnsupportedImage)?;
+    Ok(Pixmap::from_vec(rgba, size).ok_or(PreviewError::UnsupportedImage)?)
+}
@@ -0,0 +1,165 @@
+mod content;
+mod png;
+mod svg;
+
+use content::Content;
+use owned_ttf_parser::{AsFaceRef, GlyphId, OutlineBuilder, OwnedFace};
+use printpdf::lopdf::Document;
+use std::error::Error;
+use std::fmt;
+use tiny_skia::{ColorU8, Path, PathBuilder, Pixmap, Transform};
+
+/// Something drawn on the page, in PDF user space.
+pub enum Item {
+    Path {
+        path: Path,
+        transform: Transform,
+        fill: Option<ColorU8>,
+        stroke: Option<(ColorU8, f32)>,
+    },
+    Glyph {
+        font: usize,
+        glyph: u16,
+        transform: Transform,
+        color: ColorU8,
+    },
+    Image {
+        image: Pixmap,
+        transform: Transform,
+    },
+}
+
+#[derive(Debug)]
+pub enum PreviewError {
+    NoPage,
+    UnsupportedFont,
+    UnsupportedImage,
+    TooLarge(u32, u32),
+}
+
+impl fmt::Display for PreviewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PreviewError::NoPage => write!(f, "The document has no page to preview"),
+            PreviewError::UnsupportedFont => write!(f, "Only embedded TrueType fonts are drawn"),
+            PreviewError::UnsupportedImage => {
+                write!(f, "Only uncompressed 8 bits RGB or gray images are drawn")
+            }
+            PreviewError::TooLarge(width, height) => {
+                write!(f, "Cannot allocate a {}x{} pixels preview", width, height)
+            }
+        }
+    }
+}
+
+impl Error for PreviewError {}
+
+#[derive(Debug)]
+pub struct InvalidDpiError {
+    dpi: String,
+}
+
+impl fmt::Display for InvalidDpiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid dpi: {}, expected a positive number", self.dpi)
+    }
+}
+
+impl Error for InvalidDpiError {}
+
+/// Makes sure a resolution is positive, for the preview to have pixels.
+pub fn check_dpi(dpi: f32) -> Result<f32, InvalidDpiError> {
+    if dpi > 0. && dpi.is_finite() {
+        Ok(dpi)
+    } else {
+        Err(InvalidDpiError {
+            dpi: dpi.to_string(),
+        })
+    }
+}
+
+/// Reads a resolution given on the command line.
+pub fn parse_dpi(dpi: &str) -> Result<f32, InvalidDpiError> {
+    dpi.parse()
+        .map_err(|_| InvalidDpiError {
+            dpi: dpi.to_string(),
+        })
+        .and_then(check_dpi)
+}
+
+/// First page of the PDF, drawn as SVG or PNG from its content stream so the
+/// preview matches the PDF shape for shape.
+pub struct Preview {
+    width: f32,
+    height: f32,
+    dpi: f32,
+    items: Vec<Item>,
+    fonts: Vec<OwnedFace>,
+}
+
+impl Preview {
+    pub fn new(document: &Document, dpi: f32) -> Result<Self, Box<dyn Error>> {
+        let page_id = *document
+            .get_pages()
+            .values()
+            .next()
+            .ok_or(PreviewError::NoPage)?;
+        let media_box = document
+            .get_dictionary(page_id)?
+            .get(b"MediaBox")?
+            .as_array()?
+            .iter()
+            .map(|value| value.as_float().unwrap_or(0.) as f32)
+            .collect::<Vec<_>>();
+        let (width, height) = match media_box.as_slice() {
+            [left, bottom, right, top] => (right - left, top - bottom),
+            _ => return Err(PreviewError::NoPage.into()),
+        };
+        let Content { items, fonts, .. } = Content::from_page(document, page_id)?;
+        Ok(Self {
+            width,
+            height,
+            dpi,
+            items,
+            fonts,
+        })
+    }
+
+    /// Pixels per point.
+    fn scale(&self) -> f32 {
+        self.dpi / 72.
+    }
+
+    /// Outline of a glyph, in font units.
+    fn glyph_path(&self, font: usize, glyph: u16) -> Option<Path> {
+        let mut outline = Outline(PathBuilder::new());
+        self.fonts[font]
+            .as_face_ref()
+            .outline_glyph(GlyphId(glyph), &mut outline)?;
+        outline.0.finish()
+    }
+}
+
+struct Outline(PathBuilder);
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
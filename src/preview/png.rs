@@ -0,0 +1,74 @@
+use super::{Item, Preview, PreviewError};
+use std::error::Error;
+use std::path::PathBuf;
+use tiny_skia::{
+    Color, ColorU8, FillRule, FilterQuality, Paint, Pixmap, PixmapPaint, Stroke, Transform,
+};
+
+impl Preview {
+    pub fn save_png(&self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
+        debug!("Generating png: {:?}", filename);
+        self.rasterize()?.save_png(filename)?;
+        Ok(())
+    }
+
+    fn rasterize(&self) -> Result<Pixmap, PreviewError> {
+        let scale = self.scale();
+        let width = (self.width * scale).ceil() as u32;
+        let height = (self.height * scale).ceil() as u32;
+        let mut pixmap = Pixmap::new(width, height).ok_or(PreviewError::TooLarge(width, height))?;
+        pixmap.fill(Color::WHITE);
+
+        // PDF user space has its origin at the bottom left corner
+        let page = Transform::from_row(scale, 0., 0., -scale, 0., self.height * scale);
+        self.items.iter().for_each(|item| match item {
+            Item::Path {
+                path,
+                transform,
+                fill,
+                stroke,
+            } => {
+                let transform = page.pre_concat(*transform);
+                if let Some(color) = fill {
+                    pixmap.fill_path(path, &paint(*color), FillRule::Winding, transform, None);
+                }
+                if let Some((color, width)) = stroke {
+                    let stroke = Stroke {
+                        width: *width,
+                        ..Stroke::default()
+                    };
+                    pixmap.stroke_path(path, &paint(*color), &stroke, transform, None);
+                }
+            }
+            Item::Glyph {
+                font,
+                glyph,
+                transform,
+                color,
+            } => {
+                if let Some(path) = self.glyph_path(*font, *glyph) {
+                    let transform = page.pre_concat(*transform);
+                    pixmap.fill_path(&path, &paint(*color), FillRule::Winding, transform, None);
+                }
+            }
+            Item::Image { image, transform } => {
+                let (width, height) = (image.width() as f32, image.height() as f32);
+                let unit_square = Transform::from_row(1. / width, 0., 0., -1. / height, 0., 1.);
+                let paint = PixmapPaint {
+                    quality: FilterQuality::Bicubic,
+                    ..PixmapPaint::default()
+                };
+                let transform = page.pre_concat(*transform).pre_concat(unit_square);
+                pixmap.draw_pixmap(0, 0, image.as_ref(), &paint, transform, None);
+            }
+        });
+        Ok(pixmap)
+    }
+}
+
+fn paint(color: ColorU8) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.red(), color.green(), color.blue(), color.alpha());
+    paint.anti_alias = true;
+    paint
+}
@@ -0,0 +1,147 @@
+use super::{Item, Preview};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs::write;
+use std::path::PathBuf;
+use tiny_skia::{ColorU8, Path, PathSegment, Transform};
+
+impl Preview {
+    pub fn save_svg(&self, filename: &PathBuf) -> Result<(), Box<dyn Error>> {
+        debug!("Generating svg: {:?}", filename);
+        write(filename, self.render_svg()?)?;
+        Ok(())
+    }
+
+    fn render_svg(&self) -> Result<String, Box<dyn Error>> {
+        let scale = self.scale();
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            (self.width * scale).ceil(),
+            (self.height * scale).ceil(),
+            self.width,
+            self.height
+        )?;
+        writeln!(
+            svg,
+            r##"<rect width="100%" height="100%" fill="#ffffff"/>"##
+        )?;
+
+        // Every glyph is outlined once and then reused
+        let glyphs: BTreeSet<(usize, u16)> = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Glyph { font, glyph, .. } => Some((*font, *glyph)),
+                _ => None,
+            })
+            .collect();
+        let mut outlined = BTreeSet::new();
+        writeln!(svg, "<defs>")?;
+        for (font, glyph) in glyphs {
+            if let Some(path) = self.glyph_path(font, glyph) {
+                writeln!(
+                    svg,
+                    r#"<path id="g{}-{}" d="{}"/>"#,
+                    font,
+                    glyph,
+                    path_data(&path)
+                )?;
+                outlined.insert((font, glyph));
+            }
+        }
+        writeln!(svg, "</defs>")?;
+
+        // PDF user space has its origin at the bottom left corner
+        writeln!(svg, r#"<g transform="matrix(1 0 0 -1 0 {})">"#, self.height)?;
+        for item in &self.items {
+            match item {
+                Item::Path {
+                    path,
+                    transform,
+                    fill,
+                    stroke,
+                } => {
+                    write!(
+                        svg,
+                        r#"<path d="{}" transform="{}" fill="{}""#,
+                        path_data(path),
+                        matrix(*transform),
+                        fill.map(hex).unwrap_or_else(|| "none".to_string())
+                    )?;
+                    if let Some((color, width)) = stroke {
+                        write!(svg, r#" stroke="{}" stroke-width="{}""#, hex(*color), width)?;
+                    }
+                    writeln!(svg, "/>")?;
+                }
+                Item::Glyph {
+                    font,
+                    glyph,
+                    transform,
+                    color,
+                } => {
+                    if outlined.contains(&(*font, *glyph)) {
+                        writeln!(
+                            svg,
+                            r##"<use xlink:href="#g{}-{}" transform="{}" fill="{}"/>"##,
+                            font,
+                            glyph,
+                            matrix(*transform),
+                            hex(*color)
+                        )?;
+                    }
+                }
+                Item::Image { image, transform } => {
+                    let unit_square = Transform::from_row(1., 0., 0., -1., 0., 1.);
+                    writeln!(
+                        svg,
+                        r#"<image width="1" height="1" preserveAspectRatio="none" transform="{}" xlink:href="data:image/png;base64,{}"/>"#,
+                        matrix(transform.pre_concat(unit_square)),
+                        base64::encode(image.encode_png()?)
+                    )?;
+                }
+            }
+        }
+        writeln!(svg, "</g>")?;
+        writeln!(svg, "</svg>")?;
+        Ok(svg)
+    }
+}
+
+fn matrix(transform: Transform) -> String {
+    let Transform {
+        sx,
+        ky,
+        kx,
+        sy,
+        tx,
+        ty,
+    } = transform;
+    format!("matrix({} {} {} {} {} {})", sx, ky, kx, sy, tx, ty)
+}
+
+fn hex(color: ColorU8) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        color.red(),
+        color.green(),
+        color.blue()
+    )
+}
+
+fn path_data(path: &Path) -> String {
+    path.segments()
+        .map(|segment| match segment {
+            PathSegment::MoveTo(p) => format!("M{} {}", p.x, p.y),
+            PathSegment::LineTo(p) => format!("L{} {}", p.x, p.y),
+            PathSegment::QuadTo(p1, p) => format!("Q{} {} {} {}", p1.x, p1.y, p.x, p.y),
+            PathSegment::CubicTo(p1, p2, p) => {
+                format!("C{} {} {} {} {} {}", p1.x, p1.y, p2.x, p2.y, p.x, p.y)
+            }
+            PathSegment::Close => "Z".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("")
+}
@@ -0,0 +1,130 @@
+use chrono::format::ParseResult;
+use chrono::{Local, NaiveDate};
+use serde::{de::Error, Deserialize, Deserializer};
+use std::str::FromStr;
+
+const FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precision {
+    Year,
+    Month,
+    Day,
+}
+
+/// A date as written in the resume: `YYYY`, `YYYY-MM` or `YYYY-MM-DD`.
+///
+/// Partial dates stand for the first day of their period and remember their
+/// precision so they are never displayed with made up components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    date: NaiveDate,
+    precision: Precision,
+}
+
+impl Date {
+    /// Whole years elapsed since the date.
+    pub fn age(&self) -> i64 {
+        Local::now()
+            .naive_utc()
+            .date()
+            .signed_duration_since(self.date)
+            .num_weeks()
+            / 52
+    }
+
+    /// Formats the date with a `strftime` like format, leaving out the
+    /// specifiers finer than the date precision, so `"%d %b %Y"` gives
+    /// `"2015"` for a date only known by its year.
+    pub fn format(&self, format: &str) -> String {
+        let mut kept = String::new();
+        for (i, group) in format.split('%').enumerate() {
+            let specifier = group.chars().next();
+            let finer = match (i, specifier) {
+                (0, _) | (_, None) => false,
+                (_, Some(specifier)) => specifier_precision(specifier) > self.precision,
+            };
+            if !finer {
+                if i > 0 {
+                    kept.push('%');
+                }
+                kept.push_str(group);
+            }
+        }
+        self.date
+            .format(&kept)
+            .to_string()
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_string()
+    }
+}
+
+impl FromStr for Date {
+    type Err = chrono::ParseError;
+
+    fn from_str(text: &str) -> ParseResult<Self> {
+        let text = text.trim();
+        let (completed, precision) = match text.matches('-').count() {
+            0 => (format!("{}-01-01", text), Precision::Year),
+            1 => (format!("{}-01", text), Precision::Month),
+            _ => (text.to_string(), Precision::Day),
+        };
+        let date = NaiveDate::parse_from_str(&completed, FORMAT)?;
+        Ok(Self { date, precision })
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let time: String = String::deserialize(deserializer)?;
+        time.parse().map_err(D::Error::custom)
+    }
+}
+
+fn specifier_precision(specifier: char) -> Precision {
+    match specifier {
+        'd' | 'e' | 'j' | 'a' | 'A' | 'u' | 'w' | 'D' | 'F' | 'x' | 'c' => Precision::Day,
+        'b' | 'B' | 'h' | 'm' => Precision::Month,
+        _ => Precision::Year,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> Date {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_each_precision() {
+        let year = date("2015");
+        assert_eq!(year.date, NaiveDate::from_ymd_opt(2015, 1, 1).unwrap());
+        assert_eq!(year.precision, Precision::Year);
+
+        let month = date("2015-06");
+        assert_eq!(month.date, NaiveDate::from_ymd_opt(2015, 6, 1).unwrap());
+        assert_eq!(month.precision, Precision::Month);
+
+        let day = date(" 2015-06-21 ");
+        assert_eq!(day.date, NaiveDate::from_ymd_opt(2015, 6, 21).unwrap());
+        assert_eq!(day.precision, Precision::Day);
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert!("2015-13".parse::<Date>().is_err());
+        assert!("June 2015".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn formats_down_to_the_precision() {
+        assert_eq!(date("2015").format("%b %Y"), "2015");
+        assert_eq!(date("2015-06").format("%b %Y"), "Jun 2015");
+        assert_eq!(date("2015-06-21").format("%b %Y"), "Jun 2015");
+        assert_eq!(date("2015").format("%d %b %Y"), "2015");
+        assert_eq!(date("2015-06").format("%d %b %Y"), "Jun 2015");
+        assert_eq!(date("2015-06-21").format("%d %b %Y"), "21 Jun 2015");
+    }
+}
@@ -0,0 +1,345 @@
+mod date;
+mod tags;
+
+use crate::pdf::Layout;
+use crate::theme::Theme;
+pub use date::Date;
+use relative_path::RelativePathBuf;
+use serde::Deserialize;
+use std::error;
+use std::ffi::OsStr;
+use std::fs::{read_to_string, File};
+use std::io::BufReader;
+use std::path::PathBuf;
+pub use tags::TagFilter;
+
+#[derive(Debug, Deserialize)]
+pub struct Resume {
+    pub basics: Basics,
+    #[serde(default)]
+    pub work: Vec<Work>,
+    #[serde(default)]
+    pub volunteer: Vec<Volunteer>,
+    #[serde(default)]
+    pub education: Vec<Education>,
+    #[serde(default)]
+    pub awards: Vec<Award>,
+    #[serde(default)]
+    pub publications: Vec<Publication>,
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub languages: Vec<Language>,
+    #[serde(default)]
+    pub interests: Vec<Interest>,
+    #[serde(default)]
+    pub references: Vec<Reference>,
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub certificates: Vec<Certificate>,
+    #[serde(default)]
+    pub meta: Meta,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Basics {
+    pub name: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(alias = "image")]
+    pub picture: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    #[serde(alias = "url")]
+    pub website: Option<String>,
+    pub summary: Option<String>,
+    pub location: Option<Location>,
+    pub birthday: Option<Date>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Location {
+    pub address: Option<String>,
+    #[serde(rename(deserialize = "postalCode"))]
+    pub postal_code: Option<String>,
+    pub city: Option<String>,
+    #[serde(
+        rename(deserialize = "countryCode"),
+        alias = "country_code",
+        alias = "country"
+    )]
+    pub country_code: Option<String>,
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Profile {
+    pub network: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Work {
+    #[serde(alias = "name")]
+    pub company: String,
+    #[serde(default)]
+    pub position: String,
+    #[serde(alias = "url")]
+    pub website: Option<String>,
+    pub location: Option<String>,
+    #[serde(rename(deserialize = "startDate"))]
+    pub start_date: Date,
+    #[serde(rename(deserialize = "endDate"))]
+    pub end_date: Option<Date>,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<Highlight>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Volunteer {
+    pub organization: String,
+    #[serde(default)]
+    pub position: String,
+    #[serde(alias = "url")]
+    pub website: Option<String>,
+    #[serde(rename(deserialize = "startDate"))]
+    pub start_date: Date,
+    #[serde(rename(deserialize = "endDate"))]
+    pub end_date: Option<Date>,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<Highlight>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Education {
+    pub institution: String,
+    #[serde(default)]
+    pub area: String,
+    #[serde(default, rename(deserialize = "studyType"))]
+    pub study_type: String,
+    #[serde(rename(deserialize = "startDate"))]
+    pub start_date: Date,
+    #[serde(rename(deserialize = "endDate"))]
+    pub end_date: Option<Date>,
+    #[serde(alias = "gpa")]
+    pub score: Option<String>,
+    #[serde(default)]
+    pub courses: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Award {
+    pub title: String,
+    pub date: Option<Date>,
+    #[serde(default)]
+    pub awarder: String,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Publication {
+    pub name: String,
+    #[serde(default)]
+    pub publisher: String,
+    #[serde(rename(deserialize = "releaseDate"))]
+    pub release_date: Option<Date>,
+    #[serde(alias = "url")]
+    pub website: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Skill {
+    pub name: String,
+    pub level: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Language {
+    pub language: String,
+    pub fluency: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Interest {
+    pub name: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Reference {
+    pub name: String,
+    pub reference: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Project {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<Highlight>,
+    #[serde(rename(deserialize = "startDate"))]
+    pub start_date: Option<Date>,
+    #[serde(rename(deserialize = "endDate"))]
+    pub end_date: Option<Date>,
+    #[serde(alias = "url")]
+    pub website: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Certificate {
+    pub name: String,
+    pub date: Option<Date>,
+    #[serde(default)]
+    pub issuer: String,
+    #[serde(alias = "url")]
+    pub website: Option<String>,
+}
+
+/// Highlight written either as its text or as a `{ text, tags }` table.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(from = "HighlightDefinition")]
+pub struct Highlight {
+    pub text: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HighlightDefinition {
+    Text(String),
+    Tagged {
+        text: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Meta {
+    pub version: Option<String>,
+    #[serde(rename(deserialize = "lastModified"))]
+    pub last_modified: Option<String>,
+    pub theme: Option<MetaTheme>,
+    /// Sections of the PDF regions, overriding those of the template
+    #[serde(default)]
+    pub sections: Layout,
+}
+
+/// JSON Resume tooling stores a theme name where we expect a theme section.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum MetaTheme {
+    Name(String),
+    Section(Box<Theme>),
+}
+
+impl Resume {
+    pub fn from_path(path: PathBuf) -> Result<Self, Box<dyn error::Error>> {
+        debug!("Opening resume: {:?}", path);
+        let mut resume: Resume = match path.extension().and_then(OsStr::to_str) {
+            Some("toml") => toml::from_str(&read_to_string(&path)?)?,
+            _ => {
+                let file = File::open(&path)?;
+                let reader = BufReader::new(file);
+                serde_json::from_reader(reader)?
+            }
+        };
+        debug!(
+            "Resume version: {:?}, last modified: {:?}",
+            resume.meta.version, resume.meta.last_modified
+        );
+        if let Some(MetaTheme::Section(theme)) = &mut resume.meta.theme {
+            theme.fonts = theme.fonts.clone().relative_to(path.parent().unwrap());
+        }
+        if let Some(picture) = &mut resume.basics.picture {
+            *picture = RelativePathBuf::from_path(path.parent().unwrap())?
+                .join(&picture)
+                .normalize()
+                .as_str()
+                .to_string();
+        }
+        Ok(resume)
+    }
+
+    pub fn theme(&self) -> Theme {
+        match &self.meta.theme {
+            None => Theme::default(),
+            Some(MetaTheme::Section(theme)) => theme.as_ref().clone(),
+            Some(MetaTheme::Name(name)) => {
+                warn!(
+                    "Ignoring theme {:?}, only theme sections are supported",
+                    name
+                );
+                Theme::default()
+            }
+        }
+    }
+}
+
+impl From<HighlightDefinition> for Highlight {
+    fn from(definition: HighlightDefinition) -> Self {
+        match definition {
+            HighlightDefinition::Text(text) => Self { text, tags: vec![] },
+            HighlightDefinition::Tagged { text, tags } => Self { text, tags },
+        }
+    }
+}
+
+impl From<Highlight> for String {
+    fn from(highlight: Highlight) -> Self {
+        highlight.text
+    }
+}
+
+impl Location {
+    /// Postal address lines, leaving out the unknown parts.
+    pub fn address_lines(&self) -> Vec<String> {
+        let join = |parts: &[&Option<String>], separator: &str| {
+            parts
+                .iter()
+                .filter_map(|part| part.as_deref())
+                .filter(|part| !part.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join(separator)
+        };
+        let mut lines: Vec<String> = self
+            .address
+            .iter()
+            .flat_map(|address| address.lines())
+            .map(str::to_string)
+            .collect();
+        lines.push(join(&[&self.postal_code, &self.city], " "));
+        lines.push(join(&[&self.region, &self.country_code], ", "));
+        lines.retain(|line| !line.trim().is_empty());
+        lines
+    }
+}
+
+/// `tel:` link calling `phone`.
+pub fn phone_uri(phone: &str) -> String {
+    let number: String = phone
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '+')
+        .collect();
+    format!("tel:{}", number)
+}
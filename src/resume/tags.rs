@@ -0,0 +1,166 @@
+use super::{Highlight, Resume};
+
+/// Tags deciding which entries and highlights make it into a variant of the
+/// resume. Untagged ones are always kept.
+#[derive(Debug, Clone, Default)]
+pub struct TagFilter {
+    /// Keeps only what has one of these tags
+    pub include: Vec<String>,
+    /// Leaves out what has one of these tags
+    pub exclude: Vec<String>,
+}
+
+impl TagFilter {
+    /// Whether something tagged with `tags` is kept, tags being compared
+    /// regardless of case.
+    pub fn keeps(&self, tags: &[String]) -> bool {
+        let any_of = |wanted: &[String]| {
+            tags.iter()
+                .any(|tag| wanted.iter().any(|other| other.eq_ignore_ascii_case(tag)))
+        };
+        tags.is_empty()
+            || ((self.include.is_empty() || any_of(&self.include)) && !any_of(&self.exclude))
+    }
+
+    fn retain_highlights(&self, highlights: &mut Vec<Highlight>) {
+        highlights.retain(|highlight| self.keeps(&highlight.tags));
+    }
+}
+
+impl Resume {
+    /// Leaves out the work, education, skills, projects and highlights not
+    /// kept by `filter`.
+    pub fn filter(mut self, filter: &TagFilter) -> Self {
+        self.work.retain(|work| filter.keeps(&work.tags));
+        self.work
+            .iter_mut()
+            .for_each(|work| filter.retain_highlights(&mut work.highlights));
+        self.volunteer
+            .iter_mut()
+            .for_each(|volunteer| filter.retain_highlights(&mut volunteer.highlights));
+        self.education
+            .retain(|education| filter.keeps(&education.tags));
+        self.skills.retain(|skill| filter.keeps(&skill.tags));
+        self.projects.retain(|project| filter.keeps(&project.tags));
+        self.projects
+            .iter_mut()
+            .for_each(|project| filter.retain_highlights(&mut project.highlights));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn keeps_everything_without_filter() {
+        let filter = TagFilter::default();
+        assert!(filter.keeps(&[]));
+        assert!(filter.keeps(&tags(&["backend"])));
+    }
+
+    #[test]
+    fn keeps_included_and_untagged() {
+        let filter = TagFilter {
+            include: tags(&["backend"]),
+            exclude: vec![],
+        };
+        assert!(filter.keeps(&[]));
+        assert!(filter.keeps(&tags(&["frontend", "Backend"])));
+        assert!(!filter.keeps(&tags(&["frontend"])));
+    }
+
+    #[test]
+    fn leaves_out_excluded_even_when_included() {
+        let filter = TagFilter {
+            include: tags(&["backend"]),
+            exclude: tags(&["legacy"]),
+        };
+        assert!(filter.keeps(&[]));
+        assert!(!filter.keeps(&tags(&["backend", "LEGACY"])));
+        assert!(!filter.keeps(&tags(&["legacy"])));
+    }
+
+    #[test]
+    fn filters_the_resume() {
+        let resume: Resume = serde_json::from_str(
+            r#"{
+                "basics": { "name": "Jane Doe" },
+                "work": [
+                    {
+                        "name": "Acme",
+                        "startDate": "2019",
+                        "highlights": [
+                            "Mentoring",
+                            { "text": "Rust services", "tags": ["backend"] },
+                            { "text": "React pages", "tags": ["frontend"] }
+                        ]
+                    },
+                    { "name": "Shop", "startDate": "2016", "tags": ["frontend"] }
+                ],
+                "volunteer": [
+                    {
+                        "organization": "Food Bank",
+                        "startDate": "2018",
+                        "highlights": [
+                            "Logistics",
+                            { "text": "Website", "tags": ["frontend"] }
+                        ]
+                    }
+                ],
+                "education": [
+                    { "institution": "KTH", "startDate": "2010", "tags": ["backend"] },
+                    { "institution": "Art School", "startDate": "2008", "tags": ["design"] }
+                ],
+                "skills": [
+                    { "name": "Rust", "tags": ["backend"] },
+                    { "name": "CSS", "tags": ["frontend"] },
+                    { "name": "Git" }
+                ],
+                "projects": [
+                    {
+                        "name": "Crate",
+                        "tags": ["backend"],
+                        "highlights": [{ "text": "Demo page", "tags": ["frontend"] }]
+                    },
+                    { "name": "Theme", "tags": ["frontend"] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let filter = TagFilter {
+            include: tags(&["backend"]),
+            exclude: vec![],
+        };
+        let resume = resume.filter(&filter);
+
+        let texts = |highlights: &[Highlight]| -> Vec<String> {
+            highlights
+                .iter()
+                .map(|highlight| highlight.text.clone())
+                .collect()
+        };
+        assert_eq!(resume.work.len(), 1);
+        assert_eq!(
+            texts(&resume.work[0].highlights),
+            ["Mentoring", "Rust services"]
+        );
+        assert_eq!(resume.volunteer.len(), 1);
+        assert_eq!(texts(&resume.volunteer[0].highlights), ["Logistics"]);
+        assert_eq!(resume.education.len(), 1);
+        assert_eq!(resume.education[0].institution, "KTH");
+        let skills: Vec<&str> = resume
+            .skills
+            .iter()
+            .map(|skill| skill.name.as_str())
+            .collect();
+        assert_eq!(skills, ["Rust", "Git"]);
+        assert_eq!(resume.projects.len(), 1);
+        assert!(resume.projects[0].highlights.is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::error;
+use std::ffi::OsStr;
+use std::fs::{read_to_string, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// Look of the generated resume.
+///
+/// Every value can be left out of a theme file, the default theme being the
+/// historical look of the resume.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub colors: Palette,
+    pub font_sizes: FontSizes,
+    pub line_heights: LineHeights,
+    pub columns: Columns,
+    pub spacing: Spacing,
+    pub highlights: Highlights,
+    pub fonts: Fonts,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    /// Left column background, timeline and right column text
+    pub primary: Color,
+    /// Left column text
+    pub secondary: Color,
+}
+
+/// Font sizes, in points
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FontSizes {
+    pub name: f64,
+    pub heading: f64,
+    pub title: f64,
+    pub text: f64,
+    pub small: f64,
+    pub icon: f64,
+}
+
+/// Line heights, in points
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LineHeights {
+    pub name: f64,
+    pub heading: f64,
+    pub list: f64,
+    pub skills: f64,
+    pub text: f64,
+}
+
+/// Column sizes, in millimetres
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Columns {
+    pub left_width: f64,
+    pub padding: f64,
+}
+
+/// Vertical spacing, in points
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Spacing {
+    pub page_margin: f64,
+    pub event: f64,
+    pub entry: f64,
+    pub section: f64,
+}
+
+/// Rendering of highlights and skill keywords
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Highlights {
+    pub style: HighlightStyle,
+    /// Glyph starting each item of a list
+    pub bullet: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HighlightStyle {
+    /// One item per line, with a hanging indent
+    List,
+    /// Inline chips, wrapping as needed
+    Chips,
+}
+
+/// Font files replacing the embedded Liberation fonts, relative to the file
+/// declaring them. The italic font falls back to the light one.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Fonts {
+    pub regular: Option<PathBuf>,
+    pub bold: Option<PathBuf>,
+    pub light: Option<PathBuf>,
+    pub italic: Option<PathBuf>,
+}
+
+/// Color written either as `"#rrggbb"` or as a `{ c, m, y, k }` table.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "ColorDefinition")]
+pub enum Color {
+    Rgb(f64, f64, f64),
+    Cmyk(f64, f64, f64, f64),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColorDefinition {
+    Hex(String),
+    Cmyk { c: f64, m: f64, y: f64, k: f64 },
+}
+
+impl Theme {
+    pub fn from_path(path: &Path) -> Result<Self, Box<dyn error::Error>> {
+        debug!("Opening theme: {:?}", path);
+        let mut theme: Theme = match path.extension().and_then(OsStr::to_str) {
+            Some("toml") => toml::from_str(&read_to_string(path)?)?,
+            _ => {
+                let file = File::open(path)?;
+                let reader = BufReader::new(file);
+                serde_json::from_reader(reader)?
+            }
+        };
+        if let Some(parent) = path.parent() {
+            theme.fonts = theme.fonts.relative_to(parent);
+        }
+        Ok(theme)
+    }
+}
+
+impl Fonts {
+    /// Fills the fonts missing from `self` with those of `other`.
+    pub fn or(self, other: Fonts) -> Self {
+        Self {
+            regular: self.regular.or(other.regular),
+            bold: self.bold.or(other.bold),
+            light: self.light.or(other.light),
+            italic: self.italic.or(other.italic),
+        }
+    }
+
+    pub fn relative_to(self, directory: &Path) -> Self {
+        let resolve = |path: Option<PathBuf>| path.map(|path| directory.join(path));
+        Self {
+            regular: resolve(self.regular),
+            bold: resolve(self.bold),
+            light: resolve(self.light),
+            italic: resolve(self.italic),
+        }
+    }
+}
+
+impl Color {
+    /// `rrggbb` hexadecimal notation of the color, CMYK ones being converted.
+    pub fn to_hex(self) -> String {
+        let (r, g, b) = match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Cmyk(c, m, y, k) => (
+                (1. - c) * (1. - k),
+                (1. - m) * (1. - k),
+                (1. - y) * (1. - k),
+            ),
+        };
+        format!(
+            "{:02x}{:02x}{:02x}",
+            (r * 255.).round() as u8,
+            (g * 255.).round() as u8,
+            (b * 255.).round() as u8
+        )
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            primary: Color::Cmyk(0.78, 0.62, 0.66, 0.71),
+            secondary: Color::Rgb(1., 1., 1.),
+        }
+    }
+}
+
+impl Default for FontSizes {
+    fn default() -> Self {
+        Self {
+            name: 20.,
+            heading: 12.,
+            title: 12.,
+            text: 10.,
+            small: 9.,
+            icon: 12.,
+        }
+    }
+}
+
+impl Default for LineHeights {
+    fn default() -> Self {
+        Self {
+            name: 18.,
+            heading: 18.,
+            list: 16.,
+            skills: 14.,
+            text: 12.,
+        }
+    }
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            left_width: 74.5,
+            padding: 5.,
+        }
+    }
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            page_margin: 20.,
+            event: 10.,
+            entry: 8.,
+            section: 40.,
+        }
+    }
+}
+
+impl Default for Highlights {
+    fn default() -> Self {
+        Self {
+            style: HighlightStyle::List,
+            bullet: "\u{2022}".to_string(),
+        }
+    }
+}
+
+impl TryFrom<ColorDefinition> for Color {
+    type Error = String;
+
+    fn try_from(definition: ColorDefinition) -> Result<Self, Self::Error> {
+        match definition {
+            ColorDefinition::Cmyk { c, m, y, k } => Ok(Color::Cmyk(c, m, y, k)),
+            ColorDefinition::Hex(hex) => {
+                let digits = hex.trim_start_matches('#');
+                let component = |i: usize| {
+                    digits
+                        .get(i..i + 2)
+                        .and_then(|component| u8::from_str_radix(component, 16).ok())
+                        .map(|component| f64::from(component) / 255.)
+                };
+                match (digits.len(), component(0), component(2), component(4)) {
+                    (6, Some(r), Some(g), Some(b)) => Ok(Color::Rgb(r, g, b)),
+                    _ => Err(format!("Invalid color: {}", hex)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use crate::resume::{Date, Education, Resume, Volunteer, Work};
+
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+
+pub const DATE_FORMAT: &str = "%b %Y";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventType {
+    Work,
+    Education,
+    Volunteer,
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub event_type: EventType,
+    pub start_date: Date,
+    pub end_date: Option<Date>,
+    pub institution: String,
+    pub website: Option<String>,
+    pub location: Option<String>,
+    pub label: String,
+    pub summary: Option<String>,
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Timeline {
+    events: Vec<Event>,
+}
+
+/// Roles of the same type at the same institution, shown under a single
+/// header.
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub events: Vec<Event>,
+}
+
+/// Legal forms left out of institution names compared once normalized.
+const LEGAL_SUFFIXES: &[&str] = &[
+    "ab",
+    "ag",
+    "bv",
+    "co",
+    "corp",
+    "corporation",
+    "gmbh",
+    "inc",
+    "llc",
+    "llp",
+    "ltd",
+    "limited",
+    "plc",
+    "sa",
+    "sarl",
+    "sas",
+];
+
+impl Event {
+    /// Institution name and its location, the latter being read from a
+    /// `"Company, City"` institution when not given explicitly.
+    pub fn institution_and_location(&self) -> (&str, String) {
+        match &self.location {
+            Some(location) => (&self.institution, location.clone()),
+            None => {
+                let mut split_iter = self.institution.split(',');
+                let name = split_iter.next().unwrap();
+                let location = split_iter.collect::<Vec<_>>().join(",");
+                (name, location)
+            }
+        }
+    }
+}
+
+impl Group {
+    /// Earliest start and latest end of the roles, no end meaning one of them
+    /// is ongoing.
+    pub fn period(&self) -> (Date, Option<Date>) {
+        let start_date = self
+            .events
+            .iter()
+            .map(|event| event.start_date)
+            .min()
+            .unwrap();
+        let end_date = self
+            .events
+            .iter()
+            .map(|event| event.end_date)
+            .try_fold(None, |latest: Option<Date>, end_date| {
+                end_date.map(|end_date| latest.max(Some(end_date)))
+            })
+            .flatten();
+        (start_date, end_date)
+    }
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self { events: vec![] }
+    }
+
+    /// Work, education and volunteer events of the resume, the latter being
+    /// returned apart when `volunteer_section` is set.
+    pub fn from_resume(resume: &Resume, volunteer_section: bool) -> (Self, Self) {
+        let mut timeline = Timeline::new();
+        let mut volunteering = Timeline::new();
+
+        resume.work.iter().cloned().for_each(|work| {
+            timeline.add(Event::from(work));
+        });
+
+        resume.education.iter().cloned().for_each(|education| {
+            timeline.add(Event::from(education));
+        });
+
+        resume.volunteer.iter().cloned().for_each(|volunteer| {
+            let event = Event::from(volunteer);
+            if volunteer_section {
+                volunteering.add(event);
+            } else {
+                timeline.add(event);
+            }
+        });
+        (timeline, volunteering)
+    }
+
+    pub fn add(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        let mut events: Vec<Event> = self.events.to_vec();
+        events.sort_by(|a, b| {
+            let by_end = match (a.end_date, b.end_date) {
+                (None, None) => Ordering::Equal,
+                (None, _) => Ordering::Less,
+                (_, None) => Ordering::Greater,
+                (Some(a), Some(b)) => b.cmp(&a),
+            };
+            by_end.then_with(|| b.start_date.cmp(&a.start_date))
+        });
+        events
+    }
+
+    /// Sorted events, those of the same type at the same institution being
+    /// grouped wherever they fall. The groups follow their latest event, so
+    /// they are sorted by their latest period. With `normalize`, institution
+    /// names only differing by case, punctuation or legal form are the same.
+    pub fn groups(&self, normalize: bool) -> Vec<Group> {
+        let key = |event: &Event| {
+            let (name, _) = event.institution_and_location();
+            if normalize {
+                normalize_institution(name)
+            } else {
+                name.trim().to_string()
+            }
+        };
+        let mut groups: Vec<Group> = vec![];
+        self.events().into_iter().for_each(|event| {
+            let group = groups.iter_mut().find(|group| {
+                group.events[0].event_type == event.event_type
+                    && key(&group.events[0]) == key(&event)
+            });
+            match group {
+                Some(group) => group.events.push(event),
+                None => groups.push(Group {
+                    events: vec![event],
+                }),
+            }
+        });
+        groups
+    }
+}
+
+/// Lowercase alphanumeric words of an institution name, its legal form left
+/// out.
+pub fn normalize_institution(name: &str) -> String {
+    let lowercase = name.to_lowercase();
+    let mut words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    while words.len() > 1 && LEGAL_SUFFIXES.contains(words.last().unwrap()) {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+impl From<Work> for Event {
+    fn from(work: Work) -> Self {
+        Self {
+            event_type: EventType::Work,
+            start_date: work.start_date,
+            end_date: work.end_date,
+            institution: work.company,
+            website: work.website,
+            location: work.location,
+            label: work.position,
+            summary: work.summary,
+            highlights: work.highlights.into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl From<Education> for Event {
+    fn from(education: Education) -> Self {
+        let mut label = match (education.study_type.is_empty(), education.area.is_empty()) {
+            (false, false) => format!("{} in {}", education.study_type, education.area),
+            (true, _) => education.area,
+            (false, true) => education.study_type,
+        };
+        if let Some(score) = education.score {
+            label = format!("{} ({})", label, score);
+        }
+        Self {
+            event_type: EventType::Education,
+            start_date: education.start_date,
+            end_date: education.end_date,
+            institution: education.institution,
+            website: None,
+            location: None,
+            label,
+            summary: None,
+            highlights: education.courses,
+        }
+    }
+}
+
+impl From<Volunteer> for Event {
+    fn from(volunteer: Volunteer) -> Self {
+        Self {
+            event_type: EventType::Volunteer,
+            start_date: volunteer.start_date,
+            end_date: volunteer.end_date,
+            institution: volunteer.organization,
+            website: volunteer.website,
+            location: None,
+            label: volunteer.position,
+            summary: volunteer.summary,
+            highlights: volunteer.highlights.into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl FromIterator<Event> for Timeline {
+    fn from_iter<I: IntoIterator<Item = Event>>(events: I) -> Self {
+        Self {
+            events: events.into_iter().collect(),
+        }
+    }
+}
+
+pub fn format_period(start_date: &Date, end_date: Option<&Date>) -> String {
+    let end_date = end_date
+        .map(|end_date| end_date.format(DATE_FORMAT))
+        .unwrap_or_else(|| "Today".to_string());
+    format!("{} - {}", start_date.format(DATE_FORMAT), end_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: EventType, institution: &str, start: &str, end: Option<&str>) -> Event {
+        Event {
+            event_type,
+            start_date: start.parse().unwrap(),
+            end_date: end.map(|end| end.parse().unwrap()),
+            institution: institution.to_string(),
+            website: None,
+            location: None,
+            label: String::new(),
+            summary: None,
+            highlights: vec![],
+        }
+    }
+
+    #[test]
+    fn groups_roles_apart_at_the_same_institution() {
+        let timeline: Timeline = vec![
+            event(EventType::Work, "Acme", "2016", Some("2019")),
+            event(EventType::Volunteer, "Food Bank", "2018", None),
+            event(EventType::Work, "Acme", "2019", None),
+        ]
+        .into_iter()
+        .collect();
+        let groups = timeline.groups(false);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].events[0].institution, "Acme");
+        assert_eq!(groups[0].events.len(), 2);
+        assert_eq!(groups[0].period().0.format(DATE_FORMAT), "2016");
+        assert_eq!(groups[0].period().1, None);
+        assert_eq!(groups[1].events[0].institution, "Food Bank");
+    }
+
+    #[test]
+    fn keeps_types_apart() {
+        let timeline: Timeline = vec![
+            event(EventType::Work, "KTH", "2010", Some("2012")),
+            event(EventType::Education, "KTH", "2005", Some("2010")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(timeline.groups(false).len(), 2);
+    }
+
+    #[test]
+    fn normalizes_institutions_on_demand() {
+        let timeline: Timeline = vec![
+            event(EventType::Work, "Acme Corp.", "2019", None),
+            event(EventType::Work, "ACME", "2016", Some("2019")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(timeline.groups(false).len(), 2);
+        assert_eq!(timeline.groups(true).len(), 1);
+    }
+}
@@ -0,0 +1,15 @@
+//! Section titles shared by the renderers.
+
+pub const ABOUT: &str = "About me";
+pub const INFO: &str = "Info";
+pub const LANGUAGES: &str = "Spoken languages";
+pub const SOCIALS: &str = "Social";
+pub const SKILLS: &str = "Skills";
+pub const TIMELINE: &str = "Timeline";
+pub const VOLUNTEERING: &str = "Volunteering";
+pub const PROJECTS: &str = "Projects";
+pub const AWARDS: &str = "Awards";
+pub const CERTIFICATES: &str = "Certificates";
+pub const PUBLICATIONS: &str = "Publications";
+pub const INTERESTS: &str = "Interests";
+pub const REFERENCES: &str = "References";